@@ -1,128 +1,519 @@
-use std::{collections::HashMap, sync::Arc};
+mod conditional;
+mod config;
+mod error;
+pub mod eurofxref;
+mod http;
+mod inflation;
+mod interest_rates;
+mod metadata;
+mod retry;
+mod sdmx_csv;
+mod sdmx_json;
+mod sdmx_xml;
+mod series;
+mod status;
+mod structure;
 
-use anyhow::{Result, anyhow};
-use http_client::{HttpClient, Request, RequestBuilderExt, ResponseAsyncBodyExt};
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use http_client::HttpClient;
 use serde::{Deserialize, Serialize};
 
+use crate::series::validate_period;
+pub use crate::{
+    conditional::{CacheValidators, Conditional},
+    config::{BceClientConfig, BceClientConfigBuilder},
+    error::{BoxError, ClientError, Result},
+    eurofxref::EuroFxRefClient,
+    inflation::{EURO_AREA, HicpMeasure, HicpObservation, adjust_for_inflation},
+    interest_rates::{InterestRateObservation, PolicyRate},
+    metadata::SeriesMetadata,
+    retry::RetryPolicy,
+    sdmx_csv::{SeriesRates, SeriesStream},
+    series::{ExrKey, ExrSeries, Frequency, SeriesVariation},
+    status::{ObservationConfidentiality, ObservationStatus},
+    structure::{Code, Codelist, Dataflow, Structures, parse_structures},
+};
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExchangeRate {
     pub currency: String,
-    pub rate: f64,
+    /// `None` for an observation published without a value, whose `status`
+    /// says why, typically `Missing`.
+    pub rate: Option<f64>,
+    /// The ECB fixing (`TIME_PERIOD`) this rate belongs to. For monthly,
+    /// quarterly and annual series this is the first day of the period.
+    pub observation_date: NaiveDate,
+    /// `OBS_STATUS` of the observation.
+    #[serde(default)]
+    pub status: ObservationStatus,
+    /// `OBS_CONF` of the observation, when the source reports it.
+    #[serde(default)]
+    pub confidentiality: Option<ObservationConfidentiality>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "StoredSnapshot")]
 pub struct ExchangeRatesSnapshot {
     pub rates: Vec<ExchangeRate>,
+    /// The most recent fixing among `rates`.
+    pub observation_date: NaiveDate,
+    /// When the snapshot was fetched from the ECB.
     pub timestamp: String,
+    /// Metadata of the series `rates` were taken from, when the source
+    /// publishes it.
+    #[serde(default)]
+    pub series: Vec<SeriesMetadata>,
 }
 
+/// Stored form of an [`ExchangeRatesSnapshot`]. Snapshots stored before
+/// rates were dated have no observation date, neither on the snapshot nor on
+/// its rates: the date they were fetched on stands in for it.
 #[derive(Deserialize)]
-struct EcbDataResponse {
-    #[serde(rename = "dataSets")]
-    data_sets: Vec<DataSet>,
-    structure: Structure,
+struct StoredSnapshot {
+    rates: Vec<StoredRate>,
+    observation_date: Option<NaiveDate>,
+    timestamp: String,
+    #[serde(default)]
+    series: Vec<SeriesMetadata>,
 }
 
 #[derive(Deserialize)]
-struct DataSet {
-    series: HashMap<String, Series>,
+struct StoredRate {
+    currency: String,
+    rate: Option<f64>,
+    observation_date: Option<NaiveDate>,
+    #[serde(default)]
+    status: ObservationStatus,
+    #[serde(default)]
+    confidentiality: Option<ObservationConfidentiality>,
 }
 
-#[derive(Deserialize)]
-struct Series {
-    observations: HashMap<String, Vec<Option<f64>>>,
+impl TryFrom<StoredSnapshot> for ExchangeRatesSnapshot {
+    type Error = String;
+
+    fn try_from(stored: StoredSnapshot) -> std::result::Result<Self, Self::Error> {
+        let observation_date = match stored.observation_date {
+            Some(observation_date) => observation_date,
+            None => DateTime::parse_from_rfc3339(&stored.timestamp)
+                .map_err(|e| format!("Invalid snapshot timestamp {}: {}", stored.timestamp, e))?
+                .date_naive(),
+        };
+
+        Ok(Self {
+            rates: stored
+                .rates
+                .into_iter()
+                .map(|rate| ExchangeRate {
+                    currency: rate.currency,
+                    rate: rate.rate,
+                    observation_date: rate.observation_date.unwrap_or(observation_date),
+                    status: rate.status,
+                    confidentiality: rate.confidentiality,
+                })
+                .collect(),
+            observation_date,
+            timestamp: stored.timestamp,
+            series: stored.series,
+        })
+    }
 }
 
-#[derive(Deserialize)]
-struct Structure {
-    dimensions: Dimensions,
+/// Rates decoded from an SDMX data message, together with the metadata of the
+/// series they belong to.
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeRatesData {
+    pub rates: Vec<ExchangeRate>,
+    pub series: Vec<SeriesMetadata>,
 }
 
-#[derive(Deserialize)]
-struct Dimensions {
-    series: Vec<Dimension>,
+impl ExchangeRatesSnapshot {
+    /// Builds a snapshot dated after its most recent rate. Returns `None` when
+    /// `rates` is empty, as there is no fixing to date it with.
+    pub fn from_rates(mut rates: Vec<ExchangeRate>, timestamp: String) -> Option<Self> {
+        let observation_date = rates.iter().map(|rate| rate.observation_date).max()?;
+
+        rates.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+        Some(Self {
+            rates,
+            observation_date,
+            timestamp,
+            series: Vec::new(),
+        })
+    }
+
+    /// Attaches the metadata of the series this snapshot has rates for.
+    pub fn with_series_metadata(mut self, series: &[SeriesMetadata]) -> Self {
+        self.series = series
+            .iter()
+            .filter(|metadata| {
+                self.rates
+                    .iter()
+                    .any(|rate| rate.currency == metadata.currency)
+            })
+            .cloned()
+            .collect();
+        self
+    }
+
+    /// Overlays the rates and series metadata of `newer`, e.g. revised
+    /// observations of the same period, on this snapshot. Currencies missing
+    /// from `newer` are kept as they are.
+    pub fn merged_with(self, newer: ExchangeRatesSnapshot) -> Self {
+        let kept_rates = self
+            .rates
+            .into_iter()
+            .filter(|rate| !newer.rates.iter().any(|r| r.currency == rate.currency))
+            .collect::<Vec<_>>();
+        let kept_series = self
+            .series
+            .into_iter()
+            .filter(|metadata| !newer.series.iter().any(|s| s.currency == metadata.currency))
+            .collect::<Vec<_>>();
+
+        let mut rates = newer.rates;
+        rates.extend(kept_rates);
+        rates.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+        let mut series = newer.series;
+        series.extend(kept_series);
+
+        Self {
+            observation_date: rates
+                .iter()
+                .map(|rate| rate.observation_date)
+                .max()
+                .unwrap_or(newer.observation_date),
+            rates,
+            timestamp: newer.timestamp,
+            series,
+        }
+    }
+
+    pub fn series_metadata(&self, currency: &str) -> Option<&SeriesMetadata> {
+        self.series
+            .iter()
+            .find(|metadata| metadata.currency == currency)
+    }
 }
 
-#[derive(Deserialize)]
-struct Dimension {
-    id: String,
-    values: Vec<DimensionValue>,
+/// Wire format requested from the ECB data API. All formats decode into the
+/// same [`ExchangeRate`] values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataFormat {
+    /// SDMX-JSON (`format=jsondata`).
+    #[default]
+    JsonData,
+    /// SDMX-CSV (`format=csvdata`), noticeably smaller for long histories.
+    CsvData,
+    /// SDMX-ML 2.1 generic data message (`format=genericdata`).
+    GenericXml,
+    /// SDMX-ML 2.1 structure-specific data message
+    /// (`format=structurespecificdata`).
+    StructureSpecificXml,
 }
 
-#[derive(Deserialize)]
-struct DimensionValue {
-    id: String,
+impl DataFormat {
+    fn as_query_value(&self) -> &'static str {
+        match self {
+            DataFormat::JsonData => "jsondata",
+            DataFormat::CsvData => "csvdata",
+            DataFormat::GenericXml => "genericdata",
+            DataFormat::StructureSpecificXml => "structurespecificdata",
+        }
+    }
+
+    /// Decodes an EXR data message in this format, for instance an archived
+    /// SDMX-ML file or a mirror's response.
+    pub fn parse_exchange_rates(&self, body: &str) -> Result<ExchangeRatesData> {
+        match self {
+            DataFormat::JsonData => sdmx_json::parse_exchange_rates(
+                serde_json::from_str(body)
+                    .map_err(|e| ClientError::Parse(format!("Invalid SDMX-JSON: {}", e)))?,
+            ),
+            DataFormat::CsvData => sdmx_csv::parse_exchange_rates(body),
+            DataFormat::GenericXml | DataFormat::StructureSpecificXml => {
+                sdmx_xml::parse_exchange_rates(body)
+            }
+        }
+    }
 }
 
 pub struct BceClient {
     http_client: Arc<dyn HttpClient>,
+    config: BceClientConfig,
+    format: DataFormat,
+    retry_policy: RetryPolicy,
 }
 
 impl BceClient {
     pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
-        Self { http_client }
+        Self {
+            http_client,
+            config: BceClientConfig::default(),
+            format: DataFormat::default(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: BceClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_format(mut self, format: DataFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
     pub async fn fetch_all_exchange_rates(&self) -> Result<ExchangeRatesSnapshot> {
-        let url = "https://data-api.ecb.europa.eu/service/data/EXR/D..EUR.SP00.A?format=jsondata&lastNObservations=1";
+        self.fetch_latest_exchange_rates(ExrSeries::daily()).await
+    }
 
-        let response = self
-            .http_client
-            .send(
-                Request::builder()
-                    .method("GET")
-                    .uri(url)
-                    .header("User-Agent", "ecb-exchange-mcp/0.1.0-alpha.1")
-                    .end()?,
-            )
-            .await?;
+    /// Fetches the most recent observation of every series matching `key`,
+    /// e.g. an [`ExrSeries`] for all currencies or an [`ExrKey`] restricted to
+    /// a few.
+    pub async fn fetch_latest_exchange_rates(
+        &self,
+        key: impl Into<ExrKey>,
+    ) -> Result<ExchangeRatesSnapshot> {
+        let data = self
+            .fetch_data(&key.into(), "lastNObservations=1")
+            .await?
+            .ok_or(ClientError::NoData)?;
+
+        latest_snapshot(data)
+    }
 
-        let ecb_response: EcbDataResponse = response.json().await?;
+    /// Like [`fetch_all_exchange_rates`](Self::fetch_all_exchange_rates),
+    /// skipping the download when the latest rates have not changed since
+    /// `validators` were obtained.
+    pub async fn fetch_all_exchange_rates_if_modified(
+        &self,
+        validators: &CacheValidators,
+    ) -> Result<Conditional<ExchangeRatesSnapshot>> {
+        let url = self.data_url(&ExrKey::daily(), "lastNObservations=1");
 
-        self.parse_exchange_rates(ecb_response)
+        let (body, validators) = match http::get_if_modified(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+            validators,
+        )
+        .await?
+        {
+            http::Fetched::Body { body, validators } => (body, validators),
+            http::Fetched::NotModified => return Ok(Conditional::NotModified),
+            http::Fetched::NotFound => return Err(ClientError::NoData),
+        };
+
+        let data = self.parse_body(body)?;
+
+        Ok(Conditional::Modified {
+            value: latest_snapshot(data)?,
+            validators,
+        })
     }
 
-    fn parse_exchange_rates(&self, response: EcbDataResponse) -> Result<ExchangeRatesSnapshot> {
-        let data_set = response
-            .data_sets
-            .first()
-            .ok_or_else(|| anyhow!("No data sets found in response"))?;
+    /// Fetches the daily reference rates published between `start` and `end`
+    /// (both inclusive), returning one snapshot per observation date in
+    /// ascending order.
+    ///
+    /// Days without a fixing (weekends, TARGET holidays) have no snapshot, so
+    /// a range covering only such days yields an empty list.
+    pub async fn fetch_exchange_rates_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ExchangeRatesSnapshot>> {
+        self.fetch_series_between(ExrSeries::daily(), start, end)
+            .await
+    }
 
-        let currency_dimension = response
-            .structure
-            .dimensions
-            .series
-            .iter()
-            .find(|d| d.id == "CURRENCY")
-            .ok_or_else(|| anyhow!("CURRENCY dimension not found"))?;
-
-        let mut rates = Vec::new();
-
-        for (series_key, series) in &data_set.series {
-            let parts: Vec<&str> = series_key.split(':').collect();
-            if parts.len() >= 2 {
-                if let Ok(currency_index) = parts[1].parse::<usize>() {
-                    if let Some(currency_value) = currency_dimension.values.get(currency_index) {
-                        if let Some(rate_value) = series
-                            .observations
-                            .values()
-                            .next()
-                            .and_then(|obs| obs.first())
-                            .and_then(|val| val.to_owned())
-                        {
-                            rates.push(ExchangeRate {
-                                currency: currency_value.id.clone(),
-                                rate: rate_value,
-                            });
-                        }
-                    }
-                }
-            }
+    /// Fetches the observations of the series matching `key` for every period
+    /// overlapping `start..=end`, returning one snapshot per period in
+    /// ascending order.
+    pub async fn fetch_series_between(
+        &self,
+        key: impl Into<ExrKey>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<ExchangeRatesSnapshot>> {
+        let key = key.into();
+        let frequency = key.series().frequency;
+
+        validate_period(start, end)?;
+
+        let query = format!(
+            "startPeriod={}&endPeriod={}",
+            frequency.format_period(start),
+            frequency.format_period(end)
+        );
+
+        let Some(data) = self.fetch_data(&key, &query).await? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(snapshots_by_period(data))
+    }
+
+    /// Like [`fetch_series_between`](Self::fetch_series_between), decoding the
+    /// response one series at a time as it arrives instead of buffering it,
+    /// for pulls as large as the full daily history since 1999. The data is
+    /// always requested as SDMX-CSV, whatever format the client is set to.
+    pub async fn stream_series_between(
+        &self,
+        key: impl Into<ExrKey>,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<SeriesStream> {
+        let key = key.into();
+        let frequency = key.series().frequency;
+
+        validate_period(start, end)?;
+
+        key.validate()?;
+
+        let query = format!(
+            "startPeriod={}&endPeriod={}",
+            frequency.format_period(start),
+            frequency.format_period(end)
+        );
+        let url = self.dataflow_url("EXR", &key.key(), DataFormat::CsvData, &query);
+
+        let body = http::open(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+        )
+        .await?;
+
+        Ok(SeriesStream::new(url, body))
+    }
+
+    /// Fetches the observations of the series matching `key` that were added
+    /// or revised after `updated_after`, revisions to past periods included,
+    /// as one snapshot per period in ascending order. Each snapshot only holds
+    /// the changed observations.
+    pub async fn fetch_updated_after(
+        &self,
+        key: impl Into<ExrKey>,
+        updated_after: DateTime<Utc>,
+    ) -> Result<Vec<ExchangeRatesSnapshot>> {
+        let query = format!(
+            "updatedAfter={}",
+            updated_after.format("%Y-%m-%dT%H%%3A%M%%3A%S%%2B00%%3A00")
+        );
+
+        let Some(data) = self.fetch_data(&key.into(), &query).await? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(snapshots_by_period(data))
+    }
+
+    /// Returns `None` when the ECB answers 404, which is how the data API
+    /// reports a query that matched no observations, or 304, which is how it
+    /// reports that nothing changed since `updatedAfter`.
+    async fn fetch_data(&self, key: &ExrKey, query: &str) -> Result<Option<ExchangeRatesData>> {
+        key.validate()?;
+
+        let url = self.data_url(key, query);
+
+        match http::get_if_modified(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+            &CacheValidators::default(),
+        )
+        .await?
+        {
+            http::Fetched::Body { body, .. } => Ok(Some(self.parse_body(body)?)),
+            http::Fetched::NotModified | http::Fetched::NotFound => Ok(None),
         }
+    }
 
-        Ok(ExchangeRatesSnapshot {
-            rates,
-            timestamp: chrono::Utc::now().to_rfc3339(),
-        })
+    /// Fetches SDMX-JSON data of any dataflow, regardless of the format
+    /// configured for EXR. Returns `None` like [`fetch_data`](Self::fetch_data).
+    pub(crate) async fn fetch_series_data(
+        &self,
+        dataflow: &str,
+        key: &str,
+        query: &str,
+    ) -> Result<Option<Vec<sdmx_json::SdmxSeries>>> {
+        let url = self.dataflow_url(dataflow, key, DataFormat::JsonData, query);
+
+        let body = match http::get_if_modified(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+            &CacheValidators::default(),
+        )
+        .await?
+        {
+            http::Fetched::Body { body, .. } => http::body_to_string(body)?,
+            http::Fetched::NotModified | http::Fetched::NotFound => return Ok(None),
+        };
+
+        let response = serde_json::from_str(&body)
+            .map_err(|e| ClientError::Parse(format!("Invalid SDMX-JSON: {}", e)))?;
+
+        Ok(Some(sdmx_json::parse_series(response)?))
     }
+
+    fn data_url(&self, key: &ExrKey, query: &str) -> String {
+        self.dataflow_url("EXR", &key.key(), self.format, query)
+    }
+
+    fn dataflow_url(&self, dataflow: &str, key: &str, format: DataFormat, query: &str) -> String {
+        format!(
+            "{}/data/{}/{}?format={}&{}",
+            self.config.base_url,
+            dataflow,
+            key,
+            format.as_query_value(),
+            query
+        )
+    }
+
+    fn parse_body(&self, body: Vec<u8>) -> Result<ExchangeRatesData> {
+        let body = http::body_to_string(body)?;
+
+        self.format.parse_exchange_rates(&body)
+    }
+}
+
+fn snapshots_by_period(data: ExchangeRatesData) -> Vec<ExchangeRatesSnapshot> {
+    let mut rates_by_date: BTreeMap<NaiveDate, Vec<ExchangeRate>> = BTreeMap::new();
+    for rate in data.rates {
+        rates_by_date
+            .entry(rate.observation_date)
+            .or_default()
+            .push(rate);
+    }
+
+    let timestamp = Utc::now().to_rfc3339();
+
+    rates_by_date
+        .into_values()
+        .filter_map(|rates| ExchangeRatesSnapshot::from_rates(rates, timestamp.clone()))
+        .map(|snapshot| snapshot.with_series_metadata(&data.series))
+        .collect()
+}
+
+fn latest_snapshot(data: ExchangeRatesData) -> Result<ExchangeRatesSnapshot> {
+    ExchangeRatesSnapshot::from_rates(data.rates, Utc::now().to_rfc3339())
+        .map(|snapshot| snapshot.with_series_metadata(&data.series))
+        .ok_or(ClientError::NoData)
 }
@@ -0,0 +1,28 @@
+/// Validators the ECB returned with a response, sent back on the next request
+/// for the same resource so an unchanged payload is answered with a 304.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheValidators {
+    /// `ETag` header, sent back as `If-None-Match`.
+    pub etag: Option<String>,
+    /// `Last-Modified` header, sent back as `If-Modified-Since`.
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Outcome of a conditional request.
+#[derive(Debug, Clone)]
+pub enum Conditional<T> {
+    /// The resource changed, or no validators were sent.
+    Modified {
+        value: T,
+        validators: CacheValidators,
+    },
+    /// The server answered 304: the copy the validators came from is still
+    /// current.
+    NotModified,
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+const DEFAULT_BASE_URL: &str = "https://data-api.ecb.europa.eu/service";
+const DEFAULT_EUROFXREF_URL: &str = "https://www.ecb.europa.eu/stats/eurofxref";
+const DEFAULT_USER_AGENT: &str = concat!("bce-exchange-mcp/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Where and how the clients reach the ECB, e.g. to go through a caching
+/// proxy or to talk to a mock server in tests.
+#[derive(Debug, Clone)]
+pub struct BceClientConfig {
+    pub(crate) base_url: String,
+    pub(crate) eurofxref_url: String,
+    pub(crate) user_agent: String,
+    pub(crate) timeout: Duration,
+    pub(crate) headers: Vec<(String, String)>,
+}
+
+impl Default for BceClientConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl BceClientConfig {
+    pub fn builder() -> BceClientConfigBuilder {
+        BceClientConfigBuilder {
+            config: BceClientConfig {
+                base_url: DEFAULT_BASE_URL.to_string(),
+                eurofxref_url: DEFAULT_EUROFXREF_URL.to_string(),
+                user_agent: DEFAULT_USER_AGENT.to_string(),
+                timeout: DEFAULT_TIMEOUT,
+                headers: Vec::new(),
+            },
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn eurofxref_url(&self) -> &str {
+        &self.eurofxref_url
+    }
+
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+}
+
+pub struct BceClientConfigBuilder {
+    config: BceClientConfig,
+}
+
+impl BceClientConfigBuilder {
+    /// Root of the SDMX web service, `https://data-api.ecb.europa.eu/service`
+    /// by default. Data is requested from `{base_url}/data/...`.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.config.base_url = trim_url(base_url.into());
+        self
+    }
+
+    /// Directory holding the `eurofxref` feeds,
+    /// `https://www.ecb.europa.eu/stats/eurofxref` by default.
+    pub fn eurofxref_url(mut self, eurofxref_url: impl Into<String>) -> Self {
+        self.config.eurofxref_url = trim_url(eurofxref_url.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.config.user_agent = user_agent.into();
+        self
+    }
+
+    /// Upper bound for a single attempt, including reading the body. Timed
+    /// out attempts are retried like any other network failure.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Adds a header sent with every request, e.g. proxy credentials.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn build(self) -> BceClientConfig {
+        self.config
+    }
+}
+
+fn trim_url(url: String) -> String {
+    url.trim_end_matches('/').to_string()
+}
@@ -0,0 +1,32 @@
+use thiserror::Error;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+pub type Result<T, E = ClientError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Request to {url} failed: {source}")]
+    Network {
+        url: String,
+        #[source]
+        source: BoxError,
+    },
+    #[error("{url} responded with HTTP {status}")]
+    HttpStatus { url: String, status: u16 },
+    #[error("Failed to parse upstream response: {0}")]
+    Parse(String),
+    #[error("No exchange rates published")]
+    NoData,
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+impl ClientError {
+    pub(crate) fn network(url: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::Network {
+            url: url.into(),
+            source: source.into(),
+        }
+    }
+}
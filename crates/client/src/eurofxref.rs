@@ -0,0 +1,235 @@
+//! Client for the ECB's legacy `eurofxref` reference-rate feeds, a simpler
+//! secondary source for when the SDMX data API is unavailable.
+
+use std::{
+    collections::BTreeMap,
+    io::{Cursor, Read},
+    sync::Arc,
+};
+
+use chrono::NaiveDate;
+use http_client::HttpClient;
+use quick_xml::{Reader, events::Event};
+
+use crate::{
+    BceClientConfig, ExchangeRate, ExchangeRatesSnapshot, ObservationStatus, RetryPolicy,
+    error::{ClientError, Result},
+    http,
+};
+
+pub struct EuroFxRefClient {
+    http_client: Arc<dyn HttpClient>,
+    config: BceClientConfig,
+    retry_policy: RetryPolicy,
+}
+
+impl EuroFxRefClient {
+    pub fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self {
+            http_client,
+            config: BceClientConfig::default(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Only the `eurofxref_url` and the HTTP settings of `config` apply to
+    /// this client.
+    pub fn with_config(mut self, config: BceClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Fetches the latest fixing from `eurofxref-daily.xml`.
+    pub async fn fetch_daily(&self) -> Result<ExchangeRatesSnapshot> {
+        let body = http::body_to_string(self.fetch("eurofxref-daily.xml").await?)?;
+
+        parse_xml(&body)?.pop().ok_or(ClientError::NoData)
+    }
+
+    /// Fetches the fixings of the last 90 days from `eurofxref-hist-90d.xml`.
+    pub async fn fetch_last_90_days(&self) -> Result<Vec<ExchangeRatesSnapshot>> {
+        let body = http::body_to_string(self.fetch("eurofxref-hist-90d.xml").await?)?;
+
+        parse_xml(&body)
+    }
+
+    /// Fetches every fixing since 1999 from the `eurofxref-hist.zip` archive.
+    pub async fn fetch_history(&self) -> Result<Vec<ExchangeRatesSnapshot>> {
+        let archive = self.fetch("eurofxref-hist.zip").await?;
+
+        parse_csv(&extract_history_csv(&archive)?)
+    }
+
+    async fn fetch(&self, file_name: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/{}", self.config.eurofxref_url, file_name);
+
+        http::get(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+        )
+        .await?
+        .ok_or(ClientError::HttpStatus { url, status: 404 })
+    }
+}
+
+/// Parses a `eurofxref` XML envelope into one snapshot per `<Cube time="..">`,
+/// in ascending date order.
+pub fn parse_xml(body: &str) -> Result<Vec<ExchangeRatesSnapshot>> {
+    let mut reader = Reader::from_str(body);
+    reader.config_mut().trim_text(true);
+
+    let mut rates_by_date: BTreeMap<NaiveDate, Vec<ExchangeRate>> = BTreeMap::new();
+    let mut current_date: Option<NaiveDate> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            ClientError::Parse(format!(
+                "Invalid eurofxref XML at {}: {}",
+                reader.buffer_position(),
+                e
+            ))
+        })?;
+
+        let element = match event {
+            Event::Start(element) | Event::Empty(element) => element,
+            Event::Eof => break,
+            _ => continue,
+        };
+
+        if element.local_name().as_ref() != b"Cube" {
+            continue;
+        }
+
+        let mut time = None;
+        let mut currency = None;
+        let mut rate = None;
+
+        for attribute in element.attributes() {
+            let attribute = attribute
+                .map_err(|e| ClientError::Parse(format!("Invalid eurofxref attribute: {}", e)))?;
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| {
+                    ClientError::Parse(format!("Invalid eurofxref attribute value: {}", e))
+                })?
+                .into_owned();
+
+            match attribute.key.local_name().as_ref() {
+                b"time" => time = Some(value),
+                b"currency" => currency = Some(value),
+                b"rate" => rate = Some(value),
+                _ => {}
+            }
+        }
+
+        if let Some(time) = time {
+            let date = NaiveDate::parse_from_str(&time, "%Y-%m-%d").map_err(|e| {
+                ClientError::Parse(format!("Invalid eurofxref date {}: {}", time, e))
+            })?;
+            current_date = Some(date);
+        } else if let (Some(currency), Some(rate)) = (currency, rate) {
+            let observation_date = current_date.ok_or_else(|| {
+                ClientError::Parse(format!("Rate for {} found outside a dated cube", currency))
+            })?;
+
+            let rate = rate.parse::<f64>().map_err(|e| {
+                ClientError::Parse(format!("Invalid rate {} for {}: {}", rate, currency, e))
+            })?;
+
+            rates_by_date
+                .entry(observation_date)
+                .or_default()
+                .push(ExchangeRate {
+                    currency,
+                    rate: Some(rate),
+                    observation_date,
+                    status: ObservationStatus::Normal,
+                    confidentiality: None,
+                });
+        }
+    }
+
+    Ok(snapshots_by_date(rates_by_date))
+}
+
+/// Parses the `eurofxref-hist.csv` layout: one row per date, one column per
+/// currency, with `N/A` for currencies not quoted on that date.
+pub fn parse_csv(body: &str) -> Result<Vec<ExchangeRatesSnapshot>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(body.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ClientError::Parse(format!("Invalid eurofxref CSV header: {}", e)))?
+        .clone();
+
+    let mut rates_by_date: BTreeMap<NaiveDate, Vec<ExchangeRate>> = BTreeMap::new();
+
+    for record in reader.records() {
+        let record = record
+            .map_err(|e| ClientError::Parse(format!("Invalid eurofxref CSV record: {}", e)))?;
+
+        let Some(date) = record.get(0) else {
+            continue;
+        };
+
+        let observation_date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| ClientError::Parse(format!("Invalid eurofxref date {}: {}", date, e)))?;
+
+        let rates = rates_by_date.entry(observation_date).or_default();
+
+        for (currency, value) in headers.iter().zip(record.iter()).skip(1) {
+            if currency.is_empty() {
+                continue;
+            }
+
+            if let Ok(rate) = value.parse::<f64>() {
+                rates.push(ExchangeRate {
+                    currency: currency.to_string(),
+                    rate: Some(rate),
+                    observation_date,
+                    status: ObservationStatus::Normal,
+                    confidentiality: None,
+                });
+            }
+        }
+    }
+
+    Ok(snapshots_by_date(rates_by_date))
+}
+
+/// Extracts the single CSV file from the `eurofxref-hist.zip` archive.
+pub fn extract_history_csv(archive: &[u8]) -> Result<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(archive))
+        .map_err(|e| ClientError::Parse(format!("Invalid eurofxref archive: {}", e)))?;
+
+    let mut file = archive
+        .by_index(0)
+        .map_err(|e| ClientError::Parse(format!("Empty eurofxref archive: {}", e)))?;
+
+    let mut body = String::new();
+    file.read_to_string(&mut body)
+        .map_err(|e| ClientError::Parse(format!("Failed to read {}: {}", file.name(), e)))?;
+
+    Ok(body)
+}
+
+fn snapshots_by_date(
+    rates_by_date: BTreeMap<NaiveDate, Vec<ExchangeRate>>,
+) -> Vec<ExchangeRatesSnapshot> {
+    let timestamp = chrono::Utc::now().to_rfc3339();
+
+    rates_by_date
+        .into_values()
+        .filter_map(|rates| ExchangeRatesSnapshot::from_rates(rates, timestamp.clone()))
+        .collect()
+}
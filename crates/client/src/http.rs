@@ -0,0 +1,232 @@
+use std::{future::Future, time::Duration};
+
+use http_client::{
+    AsyncBody, HttpClient, Request, RequestBuilderExt, Response, ResponseAsyncBodyExt, StatusCode,
+};
+
+use crate::{
+    BceClientConfig,
+    conditional::CacheValidators,
+    error::{ClientError, Result},
+    retry::{RetryPolicy, parse_retry_after},
+};
+
+pub(crate) enum Fetched<B = Vec<u8>> {
+    Body {
+        body: B,
+        validators: CacheValidators,
+    },
+    NotModified,
+    NotFound,
+}
+
+struct Failure {
+    error: ClientError,
+    retryable: bool,
+    retry_after: Option<Duration>,
+}
+
+/// Sends a GET request to `url`, retrying according to `retry_policy`, and
+/// returns the response body, or `None` when the server answers 404.
+pub(crate) async fn get(
+    http_client: &dyn HttpClient,
+    url: &str,
+    config: &BceClientConfig,
+    retry_policy: &RetryPolicy,
+) -> Result<Option<Vec<u8>>> {
+    match get_if_modified(
+        http_client,
+        url,
+        config,
+        retry_policy,
+        &CacheValidators::default(),
+    )
+    .await?
+    {
+        Fetched::Body { body, .. } => Ok(Some(body)),
+        Fetched::NotFound => Ok(None),
+        // Nothing was sent for the server to compare with.
+        Fetched::NotModified => Err(ClientError::HttpStatus {
+            url: url.to_string(),
+            status: StatusCode::NOT_MODIFIED.as_u16(),
+        }),
+    }
+}
+
+/// Like [`get`], sending `validators` as `If-None-Match` and
+/// `If-Modified-Since` and reporting a 304 as [`Fetched::NotModified`].
+pub(crate) async fn get_if_modified(
+    http_client: &dyn HttpClient,
+    url: &str,
+    config: &BceClientConfig,
+    retry_policy: &RetryPolicy,
+    validators: &CacheValidators,
+) -> Result<Fetched> {
+    with_retries(url, config, retry_policy, || async {
+        match send_once(http_client, url, config, validators).await? {
+            Fetched::Body {
+                body: response,
+                validators,
+            } => {
+                let body = response.bytes().await.map_err(|e| Failure {
+                    error: ClientError::network(url, e),
+                    retryable: true,
+                    retry_after: None,
+                })?;
+
+                Ok(Fetched::Body { body, validators })
+            }
+            Fetched::NotModified => Ok(Fetched::NotModified),
+            Fetched::NotFound => Ok(Fetched::NotFound),
+        }
+    })
+    .await
+}
+
+/// Like [`get`], handing over the body unread so that it can be decoded as it
+/// arrives. Only getting the response is retried and bound by the configured
+/// timeout, not reading the body.
+pub(crate) async fn open(
+    http_client: &dyn HttpClient,
+    url: &str,
+    config: &BceClientConfig,
+    retry_policy: &RetryPolicy,
+) -> Result<Option<AsyncBody>> {
+    let validators = CacheValidators::default();
+
+    let fetched = with_retries(url, config, retry_policy, || {
+        send_once(http_client, url, config, &validators)
+    })
+    .await?;
+
+    match fetched {
+        Fetched::Body { body, .. } => Ok(Some(body.into_body())),
+        Fetched::NotFound => Ok(None),
+        Fetched::NotModified => Err(ClientError::HttpStatus {
+            url: url.to_string(),
+            status: StatusCode::NOT_MODIFIED.as_u16(),
+        }),
+    }
+}
+
+async fn with_retries<T, F, Fut>(
+    url: &str,
+    config: &BceClientConfig,
+    retry_policy: &RetryPolicy,
+    mut attempt_request: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, Failure>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        let failure = match tokio::time::timeout(config.timeout, attempt_request()).await {
+            Ok(Ok(value)) => return Ok(value),
+            Ok(Err(failure)) => failure,
+            Err(elapsed) => Failure {
+                error: ClientError::network(url, elapsed),
+                retryable: true,
+                retry_after: None,
+            },
+        };
+
+        let delay = failure
+            .retryable
+            .then(|| retry_policy.delay(attempt, failure.retry_after))
+            .flatten();
+
+        match delay {
+            Some(delay) => {
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            None => return Err(failure.error),
+        }
+    }
+}
+
+async fn send_once(
+    http_client: &dyn HttpClient,
+    url: &str,
+    config: &BceClientConfig,
+    validators: &CacheValidators,
+) -> std::result::Result<Fetched<Response<AsyncBody>>, Failure> {
+    let mut request = Request::builder()
+        .method("GET")
+        .uri(url)
+        .header("User-Agent", config.user_agent.as_str());
+
+    for (name, value) in &config.headers {
+        request = request.header(name.as_str(), value.as_str());
+    }
+
+    if let Some(etag) = &validators.etag {
+        request = request.header("If-None-Match", etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header("If-Modified-Since", last_modified);
+    }
+
+    let request = request.end().map_err(|e| Failure {
+        error: ClientError::InvalidRequest(e.to_string()),
+        retryable: false,
+        retry_after: None,
+    })?;
+
+    let response = http_client.send(request).await.map_err(|e| Failure {
+        error: ClientError::network(url, e),
+        retryable: true,
+        retry_after: None,
+    })?;
+
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND {
+        return Ok(Fetched::NotFound);
+    }
+    if status == StatusCode::NOT_MODIFIED {
+        return Ok(Fetched::NotModified);
+    }
+
+    if !status.is_success() {
+        let retry_after = matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        )
+        .then(|| response.headers().get("Retry-After"))
+        .flatten()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, chrono::Utc::now()));
+
+        return Err(Failure {
+            error: ClientError::HttpStatus {
+                url: url.to_string(),
+                status: status.as_u16(),
+            },
+            retryable: status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+            retry_after,
+        });
+    }
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let validators = CacheValidators {
+        etag: header("ETag"),
+        last_modified: header("Last-Modified"),
+    };
+
+    Ok(Fetched::Body {
+        body: response,
+        validators,
+    })
+}
+
+pub(crate) fn body_to_string(body: Vec<u8>) -> Result<String> {
+    String::from_utf8(body).map_err(|e| ClientError::Parse(format!("Invalid UTF-8 body: {}", e)))
+}
@@ -0,0 +1,162 @@
+//! Harmonised Index of Consumer Prices (`ICP` dataflow), headline all-items
+//! HICP per country and for the euro area.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BceClient, Frequency, ObservationStatus,
+    error::{ClientError, Result},
+    metadata,
+    sdmx_json::SdmxSeries,
+    series::{validate_codes, validate_period},
+};
+
+/// `REF_AREA` code of the euro area, in its changing composition.
+pub const EURO_AREA: &str = "U2";
+
+/// `ICP_SUFFIX` dimension of the headline HICP series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HicpMeasure {
+    /// Index level, 2015 = 100 (`INX`).
+    Index,
+    /// Annual rate of change, in percent (`ANR`).
+    AnnualRateOfChange,
+}
+
+impl HicpMeasure {
+    pub fn code(&self) -> &'static str {
+        match self {
+            HicpMeasure::Index => "INX",
+            HicpMeasure::AnnualRateOfChange => "ANR",
+        }
+    }
+}
+
+/// One monthly observation of a headline HICP series.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HicpObservation {
+    /// `REF_AREA` code, e.g. `DE` or [`EURO_AREA`].
+    pub area: String,
+    /// Label of the `REF_AREA` code, e.g. "Germany".
+    pub area_name: Option<String>,
+    pub measure: HicpMeasure,
+    /// First day of the month observed.
+    pub period: NaiveDate,
+    pub value: f64,
+    #[serde(default)]
+    pub status: ObservationStatus,
+}
+
+impl BceClient {
+    /// Fetches the monthly headline HICP `measure` of `areas` (every area when
+    /// empty) for the months overlapping `start..=end`, ordered by area, then
+    /// by period.
+    pub async fn fetch_hicp(
+        &self,
+        measure: HicpMeasure,
+        areas: &[&str],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<HicpObservation>> {
+        validate_period(start, end)?;
+
+        let query = format!(
+            "startPeriod={}&endPeriod={}",
+            Frequency::Monthly.format_period(start),
+            Frequency::Monthly.format_period(end)
+        );
+
+        let series = self
+            .fetch_series_data("ICP", &hicp_key(measure, areas)?, &query)
+            .await?
+            .unwrap_or_default();
+
+        Ok(hicp_observations(measure, series))
+    }
+
+    /// Fetches the most recent headline HICP `measure` of each of `areas`
+    /// (every area when empty).
+    pub async fn fetch_latest_hicp(
+        &self,
+        measure: HicpMeasure,
+        areas: &[&str],
+    ) -> Result<Vec<HicpObservation>> {
+        let series = self
+            .fetch_series_data("ICP", &hicp_key(measure, areas)?, "lastNObservations=1")
+            .await?
+            .ok_or(ClientError::NoData)?;
+
+        Ok(hicp_observations(measure, series))
+    }
+}
+
+/// Expresses `amount`, valued at the prices of `from`'s month, at the prices of
+/// `to`'s month, e.g. 100 EUR of 2015 in 2025 euros.
+pub fn adjust_for_inflation(
+    amount: f64,
+    from: &HicpObservation,
+    to: &HicpObservation,
+) -> Result<f64> {
+    if from.measure != HicpMeasure::Index || to.measure != HicpMeasure::Index {
+        return Err(ClientError::InvalidRequest(
+            "Inflation adjustment needs HICP index observations".to_string(),
+        ));
+    }
+
+    if from.area != to.area {
+        return Err(ClientError::InvalidRequest(format!(
+            "Cannot compare HICP of {} with HICP of {}",
+            from.area, to.area
+        )));
+    }
+
+    Ok(amount * to.value / from.value)
+}
+
+/// Key of the monthly, non seasonally adjusted all-items HICP, e.g.
+/// `M.DE+FR.N.000000.4.ANR`.
+fn hicp_key(measure: HicpMeasure, areas: &[&str]) -> Result<String> {
+    let areas = areas
+        .iter()
+        .map(|area| area.to_ascii_uppercase())
+        .collect::<Vec<_>>();
+
+    validate_codes("area", &areas)?;
+
+    Ok(format!(
+        "M.{}.N.000000.4.{}",
+        areas.join("+"),
+        measure.code()
+    ))
+}
+
+fn hicp_observations(measure: HicpMeasure, series: Vec<SdmxSeries>) -> Vec<HicpObservation> {
+    let mut observations = Vec::new();
+
+    for series in series {
+        let Some(area) = series.dimensions.get("REF_AREA") else {
+            continue;
+        };
+        let unit_multiplier = metadata::unit_multiplier(&series.attributes);
+
+        for observation in &series.observations {
+            let Some(value) = observation.value else {
+                continue;
+            };
+
+            observations.push(HicpObservation {
+                area: area.id.clone(),
+                area_name: area.name.clone(),
+                measure,
+                period: observation.time_period,
+                value: metadata::scale(value, unit_multiplier),
+                status: observation.status(),
+            });
+        }
+    }
+
+    observations.sort_by(|a, b| a.area.cmp(&b.area).then(a.period.cmp(&b.period)));
+    observations
+}
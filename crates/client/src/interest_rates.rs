@@ -0,0 +1,152 @@
+//! Euro short-term rate (`EST` dataflow) and ECB key policy rates (`FM`
+//! dataflow).
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BceClient, ObservationStatus,
+    error::{ClientError, Result},
+    metadata,
+    sdmx_json::SdmxSeries,
+    series::validate_period,
+};
+
+/// €STR volume-weighted trimmed mean rate, published every TARGET business
+/// day for the previous one.
+const EURO_SHORT_TERM_RATE_KEY: &str = "B.EU000A2X2A25.WT";
+
+/// One dated observation of an interest-rate series.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InterestRateObservation {
+    /// `TIME_PERIOD` of the observation. For policy rates this is the day a
+    /// new level took effect.
+    pub date: NaiveDate,
+    /// Rate in percent per annum.
+    pub rate: f64,
+    #[serde(default)]
+    pub status: ObservationStatus,
+}
+
+/// ECB key interest rate, as published in the `FM` dataflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PolicyRate {
+    /// Deposit facility rate (`DFR`).
+    DepositFacility,
+    /// Main refinancing operations, fixed rate tenders (`MRR_FR`).
+    MainRefinancing,
+    /// Marginal lending facility rate (`MLFR`).
+    MarginalLending,
+}
+
+impl PolicyRate {
+    /// SDMX key of the series recording each change of the rate, e.g.
+    /// `D.U2.EUR.4F.KR.DFR.LEV`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            PolicyRate::DepositFacility => "D.U2.EUR.4F.KR.DFR.LEV",
+            PolicyRate::MainRefinancing => "D.U2.EUR.4F.KR.MRR_FR.LEV",
+            PolicyRate::MarginalLending => "D.U2.EUR.4F.KR.MLFR.LEV",
+        }
+    }
+}
+
+impl BceClient {
+    /// Fetches the €STR fixings published between `start` and `end` (both
+    /// inclusive), in ascending date order.
+    pub async fn fetch_euro_short_term_rate(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<InterestRateObservation>> {
+        self.fetch_interest_rates("EST", EURO_SHORT_TERM_RATE_KEY, start, end)
+            .await
+    }
+
+    /// Fetches the most recent €STR fixing.
+    pub async fn fetch_latest_euro_short_term_rate(&self) -> Result<InterestRateObservation> {
+        self.fetch_latest_interest_rate("EST", EURO_SHORT_TERM_RATE_KEY)
+            .await
+    }
+
+    /// Fetches the changes of `rate` that took effect between `start` and
+    /// `end` (both inclusive), in ascending date order. The level in force on
+    /// a given day is the last change on or before it, which may predate
+    /// `start`; use [`fetch_latest_policy_rate`](Self::fetch_latest_policy_rate)
+    /// for the current level.
+    pub async fn fetch_policy_rate(
+        &self,
+        rate: PolicyRate,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<InterestRateObservation>> {
+        self.fetch_interest_rates("FM", rate.key(), start, end)
+            .await
+    }
+
+    /// Fetches the level of `rate` currently in force, dated with the day it
+    /// took effect.
+    pub async fn fetch_latest_policy_rate(
+        &self,
+        rate: PolicyRate,
+    ) -> Result<InterestRateObservation> {
+        self.fetch_latest_interest_rate("FM", rate.key()).await
+    }
+
+    async fn fetch_interest_rates(
+        &self,
+        dataflow: &str,
+        key: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<InterestRateObservation>> {
+        validate_period(start, end)?;
+
+        let query = format!("startPeriod={}&endPeriod={}", start, end);
+
+        let series = self
+            .fetch_series_data(dataflow, key, &query)
+            .await?
+            .unwrap_or_default();
+
+        Ok(interest_rate_observations(series))
+    }
+
+    async fn fetch_latest_interest_rate(
+        &self,
+        dataflow: &str,
+        key: &str,
+    ) -> Result<InterestRateObservation> {
+        let series = self
+            .fetch_series_data(dataflow, key, "lastNObservations=1")
+            .await?
+            .ok_or(ClientError::NoData)?;
+
+        interest_rate_observations(series)
+            .pop()
+            .ok_or(ClientError::NoData)
+    }
+}
+
+fn interest_rate_observations(series: Vec<SdmxSeries>) -> Vec<InterestRateObservation> {
+    let mut observations = series
+        .into_iter()
+        .flat_map(|series| {
+            let unit_multiplier = metadata::unit_multiplier(&series.attributes);
+
+            series
+                .observations
+                .into_iter()
+                .filter_map(move |observation| {
+                    Some(InterestRateObservation {
+                        date: observation.time_period,
+                        rate: metadata::scale(observation.value?, unit_multiplier),
+                        status: observation.status(),
+                    })
+                })
+        })
+        .collect::<Vec<_>>();
+
+    observations.sort_by_key(|observation| observation.date);
+    observations
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Descriptive attributes of an EXR series, as published in the SDMX
+/// structure of the response.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct SeriesMetadata {
+    pub currency: String,
+    /// Label of the `CURRENCY` code, e.g. "Japanese yen". Only SDMX-JSON
+    /// responses carry it.
+    pub currency_name: Option<String>,
+    /// `TITLE`, e.g. "Japanese yen/Euro".
+    pub title: Option<String>,
+    /// `UNIT` code the values are expressed in.
+    pub unit: Option<String>,
+    /// `UNIT_MULT`, the power of ten published values are expressed in.
+    /// [`ExchangeRate::rate`](crate::ExchangeRate::rate) is already scaled by
+    /// it.
+    pub unit_multiplier: i32,
+    /// `DECIMALS`, the number of decimals the ECB publishes the rate with.
+    pub decimals: Option<u32>,
+}
+
+impl SeriesMetadata {
+    /// Builds metadata from series attributes keyed by SDMX attribute id.
+    pub(crate) fn from_attributes(
+        currency: String,
+        currency_name: Option<String>,
+        attributes: &HashMap<String, String>,
+    ) -> Self {
+        Self {
+            currency,
+            currency_name,
+            title: attributes.get("TITLE").cloned(),
+            unit: attributes.get("UNIT").cloned(),
+            unit_multiplier: unit_multiplier(attributes),
+            decimals: attributes
+                .get("DECIMALS")
+                .and_then(|value| value.parse().ok()),
+        }
+    }
+
+    /// Scales a published value into units according to `UNIT_MULT`.
+    pub(crate) fn scale(&self, value: f64) -> f64 {
+        scale(value, self.unit_multiplier)
+    }
+}
+
+/// `UNIT_MULT` among series attributes keyed by SDMX attribute id.
+pub(crate) fn unit_multiplier(attributes: &HashMap<String, String>) -> i32 {
+    attributes
+        .get("UNIT_MULT")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+pub(crate) fn scale(value: f64, unit_multiplier: i32) -> f64 {
+    if unit_multiplier == 0 {
+        value
+    } else {
+        value * 10f64.powi(unit_multiplier)
+    }
+}
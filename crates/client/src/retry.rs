@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+/// How failed ECB requests are retried.
+///
+/// Network failures, 5xx responses and 429 are retried with exponential
+/// backoff and jitter; other 4xx responses are not. A `Retry-After` header on
+/// 429 or 503 replaces the computed backoff, unless it asks to wait longer
+/// than `max_backoff`, in which case the request fails right away.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Backoff before the second attempt, doubled for each further one.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retrying after the given (1-based) failed `attempt`, or
+    /// `None` when the request should not be retried.
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_backoff).then_some(retry_after);
+        }
+
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_backoff);
+
+        // Equal jitter: keep half of the backoff, randomise the other half so
+        // concurrent clients do not retry in lockstep.
+        let half = backoff / 2;
+        Some(half + half.mul_f64(rand::random::<f64>()))
+    }
+}
+
+/// Parses a `Retry-After` header, given either as delay seconds or as an
+/// HTTP-date.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+
+use csv::StringRecord;
+use futures::{AsyncBufReadExt, io::BufReader};
+use http_client::AsyncBody;
+
+use crate::{
+    ExchangeRate, ExchangeRatesData, ObservationConfidentiality, ObservationStatus, SeriesMetadata,
+    error::{ClientError, Result},
+    series::parse_time_period,
+};
+
+/// Series attribute columns carried over into [`SeriesMetadata`].
+const METADATA_COLUMNS: [&str; 4] = ["TITLE", "UNIT", "UNIT_MULT", "DECIMALS"];
+
+pub(crate) fn parse_exchange_rates(body: &str) -> Result<ExchangeRatesData> {
+    let mut reader = csv::Reader::from_reader(body.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ClientError::Parse(format!("Invalid CSV header: {}", e)))?;
+    let columns = Columns::from_headers(headers)?;
+
+    let mut rates = Vec::new();
+    let mut series: BTreeMap<String, SeriesMetadata> = BTreeMap::new();
+
+    for record in reader.records() {
+        let record =
+            record.map_err(|e| ClientError::Parse(format!("Invalid CSV record: {}", e)))?;
+
+        let currency = columns.currency(&record)?;
+
+        let metadata = series
+            .entry(currency.to_string())
+            .or_insert_with(|| columns.metadata(&record, currency));
+
+        rates.push(columns.rate(&record, metadata)?);
+    }
+
+    Ok(ExchangeRatesData {
+        rates,
+        series: series.into_values().collect(),
+    })
+}
+
+/// Rates of a single series, as emitted by [`SeriesStream`].
+#[derive(Debug, Clone)]
+pub struct SeriesRates {
+    pub metadata: SeriesMetadata,
+    /// In the order the ECB sent them, ascending by date.
+    pub rates: Vec<ExchangeRate>,
+}
+
+/// SDMX-CSV response decoded one series at a time while it is being
+/// received, so that only the series being read is held in memory.
+///
+/// The ECB sends the rows of a series consecutively; should a series' rows
+/// be split up, it is emitted once per run of rows.
+pub struct SeriesStream {
+    url: String,
+    reader: Option<BufReader<AsyncBody>>,
+    columns: Option<Columns>,
+    pending: Option<(String, SeriesRates)>,
+    line: String,
+}
+
+impl SeriesStream {
+    /// `None` stands for a response without any series, such as a 404.
+    pub(crate) fn new(url: String, body: Option<AsyncBody>) -> Self {
+        Self {
+            url,
+            reader: body.map(BufReader::new),
+            columns: None,
+            pending: None,
+            line: String::new(),
+        }
+    }
+
+    /// Returns the next complete series, or `None` once the response is
+    /// exhausted.
+    pub async fn next_series(&mut self) -> Result<Option<SeriesRates>> {
+        loop {
+            let Some(record) = self.next_record().await? else {
+                return Ok(self.pending.take().map(|(_, series)| series));
+            };
+
+            let Some(columns) = &self.columns else {
+                self.columns = Some(Columns::from_headers(&record)?);
+                continue;
+            };
+
+            let currency = columns.currency(&record)?;
+            let series_key = columns.series_key(&record).unwrap_or(currency);
+
+            let finished = match &mut self.pending {
+                Some((pending_key, series)) if pending_key == series_key => {
+                    series.rates.push(columns.rate(&record, &series.metadata)?);
+                    None
+                }
+                _ => {
+                    let metadata = columns.metadata(&record, currency);
+                    let rates = vec![columns.rate(&record, &metadata)?];
+
+                    self.pending
+                        .replace((series_key.to_string(), SeriesRates { metadata, rates }))
+                }
+            };
+
+            if let Some((_, series)) = finished {
+                return Ok(Some(series));
+            }
+        }
+    }
+
+    /// Reads the next non-empty record, joining physical lines while a quoted
+    /// field is still open.
+    async fn next_record(&mut self) -> Result<Option<StringRecord>> {
+        let Some(reader) = &mut self.reader else {
+            return Ok(None);
+        };
+
+        self.line.clear();
+
+        loop {
+            let read = reader
+                .read_line(&mut self.line)
+                .await
+                .map_err(|e| ClientError::network(self.url.as_str(), e))?;
+
+            let at_end = read == 0;
+            let quotes_open = self.line.bytes().filter(|b| *b == b'"').count() % 2 == 1;
+
+            if quotes_open && !at_end {
+                continue;
+            }
+
+            if self.line.trim().is_empty() {
+                if at_end {
+                    self.reader = None;
+                    return Ok(None);
+                }
+                self.line.clear();
+                continue;
+            }
+
+            let record = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .from_reader(self.line.as_bytes())
+                .records()
+                .next()
+                .transpose()
+                .map_err(|e| ClientError::Parse(format!("Invalid CSV record: {}", e)))?;
+
+            if at_end {
+                self.reader = None;
+            }
+
+            return Ok(record);
+        }
+    }
+}
+
+/// Positions of the columns the decoder reads, resolved from the header row.
+struct Columns {
+    key: Option<usize>,
+    currency: usize,
+    time_period: usize,
+    value: usize,
+    status: Option<usize>,
+    confidentiality: Option<usize>,
+    metadata: Vec<(&'static str, usize)>,
+}
+
+impl Columns {
+    fn from_headers(headers: &StringRecord) -> Result<Self> {
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header == name)
+                .ok_or_else(|| ClientError::Parse(format!("{} column not found", name)))
+        };
+
+        Ok(Self {
+            key: column("KEY").ok(),
+            currency: column("CURRENCY")?,
+            time_period: column("TIME_PERIOD")?,
+            value: column("OBS_VALUE")?,
+            status: column("OBS_STATUS").ok(),
+            confidentiality: column("OBS_CONF").ok(),
+            metadata: METADATA_COLUMNS
+                .iter()
+                .filter_map(|name| Some((*name, column(name).ok()?)))
+                .collect(),
+        })
+    }
+
+    fn series_key<'r>(&self, record: &'r StringRecord) -> Option<&'r str> {
+        self.key.and_then(|index| record.get(index))
+    }
+
+    fn currency<'r>(&self, record: &'r StringRecord) -> Result<&'r str> {
+        record
+            .get(self.currency)
+            .ok_or_else(|| ClientError::Parse("Missing CURRENCY in CSV record".to_string()))
+    }
+
+    fn metadata(&self, record: &StringRecord, currency: &str) -> SeriesMetadata {
+        let attributes: HashMap<String, String> = self
+            .metadata
+            .iter()
+            .filter_map(|(name, index)| {
+                let value = record.get(*index).filter(|value| !value.is_empty())?;
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect();
+
+        SeriesMetadata::from_attributes(currency.to_string(), None, &attributes)
+    }
+
+    /// Decodes the observation of `record`, with or without a value.
+    fn rate(&self, record: &StringRecord, metadata: &SeriesMetadata) -> Result<ExchangeRate> {
+        let rate = record
+            .get(self.value)
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|value| value.is_finite())
+            .map(|value| metadata.scale(value));
+
+        let time_period = record
+            .get(self.time_period)
+            .ok_or_else(|| ClientError::Parse("Missing TIME_PERIOD in CSV record".to_string()))?;
+
+        let attribute = |column: Option<usize>| {
+            column
+                .and_then(|index| record.get(index))
+                .filter(|value| !value.is_empty())
+        };
+
+        Ok(ExchangeRate {
+            currency: metadata.currency.clone(),
+            rate,
+            observation_date: parse_time_period(time_period)?,
+            status: attribute(self.status)
+                .map(ObservationStatus::from_code)
+                .unwrap_or_default(),
+            confidentiality: attribute(self.confidentiality)
+                .map(ObservationConfidentiality::from_code),
+        })
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+    ExchangeRate, ExchangeRatesData, ObservationConfidentiality, ObservationStatus, SeriesMetadata,
+    error::{ClientError, Result},
+    series::parse_time_period,
+};
+
+#[derive(Deserialize)]
+pub(crate) struct EcbDataResponse {
+    #[serde(rename = "dataSets")]
+    data_sets: Vec<DataSet>,
+    structure: Structure,
+}
+
+#[derive(Deserialize)]
+struct DataSet {
+    series: HashMap<String, Series>,
+}
+
+#[derive(Deserialize)]
+struct Series {
+    #[serde(default)]
+    attributes: Vec<Option<usize>>,
+    /// Each observation is its value followed by one index per observation
+    /// attribute, in the order of `structure.attributes.observation`.
+    observations: HashMap<String, Vec<Option<f64>>>,
+}
+
+#[derive(Deserialize)]
+struct Structure {
+    dimensions: Dimensions,
+    #[serde(default)]
+    attributes: Attributes,
+}
+
+#[derive(Deserialize)]
+struct Dimensions {
+    series: Vec<Dimension>,
+    #[serde(default)]
+    observation: Vec<Dimension>,
+}
+
+#[derive(Deserialize)]
+struct Dimension {
+    id: String,
+    values: Vec<DimensionValue>,
+}
+
+#[derive(Deserialize)]
+struct DimensionValue {
+    id: String,
+    name: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct Attributes {
+    #[serde(default)]
+    series: Vec<Attribute>,
+    #[serde(default)]
+    observation: Vec<Attribute>,
+}
+
+#[derive(Deserialize)]
+struct Attribute {
+    id: String,
+    values: Vec<AttributeValue>,
+}
+
+/// Coded attributes (`UNIT`, `DECIMALS`, ...) carry an `id`; free-text ones
+/// (`TITLE`) only a `name`.
+#[derive(Deserialize)]
+struct AttributeValue {
+    id: Option<String>,
+    name: Option<String>,
+}
+
+/// A series of an SDMX-JSON data message, of any dataflow, with its key and
+/// attributes resolved against the message structure.
+pub(crate) struct SdmxSeries {
+    /// Series dimension values keyed by dimension id, e.g. `CURRENCY`.
+    pub(crate) dimensions: HashMap<String, SdmxValue>,
+    /// Series attributes keyed by attribute id, e.g. `UNIT_MULT`.
+    pub(crate) attributes: HashMap<String, String>,
+    /// Observations, with or without a value, in no particular order.
+    pub(crate) observations: Vec<SdmxObservation>,
+}
+
+pub(crate) struct SdmxValue {
+    pub(crate) id: String,
+    pub(crate) name: Option<String>,
+}
+
+pub(crate) struct SdmxObservation {
+    /// Start of the `TIME_PERIOD` the observation belongs to.
+    pub(crate) time_period: NaiveDate,
+    /// `None` when the observation was published without a value.
+    pub(crate) value: Option<f64>,
+    /// Observation attributes keyed by attribute id, e.g. `OBS_STATUS`.
+    pub(crate) attributes: HashMap<String, String>,
+}
+
+impl SdmxObservation {
+    pub(crate) fn status(&self) -> ObservationStatus {
+        self.attributes
+            .get("OBS_STATUS")
+            .map(|code| ObservationStatus::from_code(code))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn confidentiality(&self) -> Option<ObservationConfidentiality> {
+        self.attributes
+            .get("OBS_CONF")
+            .map(|code| ObservationConfidentiality::from_code(code))
+    }
+}
+
+/// Decodes every series of the first data set, whatever the dataflow.
+pub(crate) fn parse_series(response: EcbDataResponse) -> Result<Vec<SdmxSeries>> {
+    let data_set = response
+        .data_sets
+        .into_iter()
+        .next()
+        .ok_or_else(|| ClientError::Parse("No data sets found in response".to_string()))?;
+
+    let structure = &response.structure;
+
+    let time_dimension = structure
+        .dimensions
+        .observation
+        .iter()
+        .find(|d| d.id == "TIME_PERIOD")
+        .ok_or_else(|| ClientError::Parse("TIME_PERIOD dimension not found".to_string()))?;
+
+    let observation_dates = time_dimension
+        .values
+        .iter()
+        .map(|value| parse_time_period(&value.id))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut decoded = Vec::with_capacity(data_set.series.len());
+
+    for (series_key, series) in &data_set.series {
+        let Some(dimensions) = series_dimensions(structure, series_key) else {
+            continue;
+        };
+
+        let mut observations = Vec::with_capacity(series.observations.len());
+
+        for (observation_key, observation) in &series.observations {
+            let time_period = observation_key
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| observation_dates.get(index))
+                .ok_or_else(|| {
+                    ClientError::Parse(format!("Unknown observation key {}", observation_key))
+                })?;
+
+            observations.push(SdmxObservation {
+                time_period: *time_period,
+                value: observation.first().and_then(|val| val.to_owned()),
+                attributes: observation_attributes(structure, observation),
+            });
+        }
+
+        decoded.push(SdmxSeries {
+            dimensions,
+            attributes: series_attributes(structure, series),
+            observations,
+        });
+    }
+
+    Ok(decoded)
+}
+
+pub(crate) fn parse_exchange_rates(response: EcbDataResponse) -> Result<ExchangeRatesData> {
+    let mut data = ExchangeRatesData::default();
+
+    for series in parse_series(response)? {
+        let currency = series
+            .dimensions
+            .get("CURRENCY")
+            .ok_or_else(|| ClientError::Parse("CURRENCY dimension not found".to_string()))?;
+
+        let metadata = SeriesMetadata::from_attributes(
+            currency.id.clone(),
+            currency.name.clone(),
+            &series.attributes,
+        );
+
+        for observation in &series.observations {
+            data.rates.push(ExchangeRate {
+                currency: currency.id.clone(),
+                rate: observation.value.map(|value| metadata.scale(value)),
+                observation_date: observation.time_period,
+                status: observation.status(),
+                confidentiality: observation.confidentiality(),
+            });
+        }
+
+        data.series.push(metadata);
+    }
+
+    Ok(data)
+}
+
+/// Resolves a series key such as `0:3:0:0:0` into the values of the series
+/// dimensions, or `None` when it does not match the structure.
+fn series_dimensions(
+    structure: &Structure,
+    series_key: &str,
+) -> Option<HashMap<String, SdmxValue>> {
+    let indices = series_key.split(':').collect::<Vec<_>>();
+    if indices.len() != structure.dimensions.series.len() {
+        return None;
+    }
+
+    structure
+        .dimensions
+        .series
+        .iter()
+        .zip(indices)
+        .map(|(dimension, index)| {
+            let value = dimension.values.get(index.parse::<usize>().ok()?)?;
+            Some((
+                dimension.id.clone(),
+                SdmxValue {
+                    id: value.id.clone(),
+                    name: value.name.clone(),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Resolves the series' attribute indices against the structure, keyed by
+/// attribute id.
+fn series_attributes(structure: &Structure, series: &Series) -> HashMap<String, String> {
+    resolve_attributes(
+        &structure.attributes.series,
+        series.attributes.iter().copied(),
+    )
+}
+
+/// Resolves the attribute indices trailing an observation's value.
+fn observation_attributes(
+    structure: &Structure,
+    observation: &[Option<f64>],
+) -> HashMap<String, String> {
+    resolve_attributes(
+        &structure.attributes.observation,
+        observation
+            .iter()
+            .skip(1)
+            .map(|index| index.map(|index| index as usize)),
+    )
+}
+
+fn resolve_attributes(
+    attributes: &[Attribute],
+    indices: impl Iterator<Item = Option<usize>>,
+) -> HashMap<String, String> {
+    attributes
+        .iter()
+        .zip(indices)
+        .filter_map(|(attribute, index)| {
+            let value = attribute.values.get(index?)?;
+            let value = value.id.clone().or_else(|| value.name.clone())?;
+            Some((attribute.id.clone(), value))
+        })
+        .collect()
+}
@@ -0,0 +1,222 @@
+//! Decoder for SDMX-ML 2.1 data messages, in both the generic
+//! (`GenericData`) and structure-specific (`StructureSpecificData`) flavours.
+
+use std::collections::HashMap;
+
+use quick_xml::{
+    Reader,
+    events::{BytesStart, Event},
+};
+
+use crate::{
+    ExchangeRate, ExchangeRatesData, ObservationConfidentiality, ObservationStatus, SeriesMetadata,
+    error::{ClientError, Result},
+    series::parse_time_period,
+};
+
+/// Series key dimensions and series attributes. Structure-specific messages
+/// carry both as plain XML attributes of `<Series>` and do not tell them apart
+/// without the DSD, so they share a single map.
+#[derive(Default)]
+struct SeriesState {
+    values: HashMap<String, String>,
+    /// Built on the first observation, once generic messages have delivered
+    /// all series attributes.
+    metadata: Option<SeriesMetadata>,
+}
+
+#[derive(Default)]
+struct ObservationState {
+    time_period: Option<String>,
+    value: Option<String>,
+    attributes: HashMap<String, String>,
+}
+
+/// Where a generic `<Value id=".." value=".."/>` element belongs.
+#[derive(Clone, Copy)]
+enum ValueTarget {
+    Series,
+    Observation,
+}
+
+#[derive(Default)]
+struct DecoderState {
+    series: Option<SeriesState>,
+    observation: Option<ObservationState>,
+    value_target: Option<ValueTarget>,
+    data: ExchangeRatesData,
+}
+
+pub(crate) fn parse_exchange_rates(body: &str) -> Result<ExchangeRatesData> {
+    let mut reader = Reader::from_str(body);
+    reader.config_mut().trim_text(true);
+
+    let mut state = DecoderState::default();
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            ClientError::Parse(format!(
+                "Invalid SDMX-ML at {}: {}",
+                reader.buffer_position(),
+                e
+            ))
+        })?;
+
+        match event {
+            Event::Start(element) => state.start_element(&element)?,
+            Event::Empty(element) => {
+                state.start_element(&element)?;
+                state.end_element(element.local_name().as_ref())?;
+            }
+            Event::End(element) => state.end_element(element.local_name().as_ref())?,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(state.data)
+}
+
+impl DecoderState {
+    fn start_element(&mut self, element: &BytesStart) -> Result<()> {
+        match element.local_name().as_ref() {
+            b"Series" => {
+                self.series = Some(SeriesState {
+                    values: element_attributes(element)?,
+                    metadata: None,
+                });
+            }
+            b"SeriesKey" => self.value_target = Some(ValueTarget::Series),
+            b"Attributes" => {
+                self.value_target = Some(if self.observation.is_some() {
+                    ValueTarget::Observation
+                } else {
+                    ValueTarget::Series
+                });
+            }
+            b"Value" => {
+                let mut attributes = element_attributes(element)?;
+                let (Some(id), Some(value)) = (attributes.remove("id"), attributes.remove("value"))
+                else {
+                    return Ok(());
+                };
+
+                match self.value_target {
+                    Some(ValueTarget::Series) => {
+                        if let Some(series) = self.series.as_mut() {
+                            series.values.insert(id, value);
+                        }
+                    }
+                    Some(ValueTarget::Observation) => {
+                        if let Some(observation) = self.observation.as_mut() {
+                            observation.attributes.insert(id, value);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            b"Obs" => {
+                let mut attributes = element_attributes(element)?;
+                self.observation = Some(ObservationState {
+                    time_period: attributes.remove("TIME_PERIOD"),
+                    value: attributes.remove("OBS_VALUE"),
+                    attributes,
+                });
+            }
+            b"ObsDimension" => {
+                if let Some(observation) = self.observation.as_mut() {
+                    observation.time_period = element_attributes(element)?.remove("value");
+                }
+            }
+            b"ObsValue" => {
+                if let Some(observation) = self.observation.as_mut() {
+                    observation.value = element_attributes(element)?.remove("value");
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn end_element(&mut self, local_name: &[u8]) -> Result<()> {
+        match local_name {
+            b"Obs" => {
+                if let Some(observation) = self.observation.take() {
+                    self.emit_observation(observation)?;
+                }
+            }
+            b"Series" => self.series = None,
+            b"SeriesKey" | b"Attributes" => self.value_target = None,
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn emit_observation(&mut self, observation: ObservationState) -> Result<()> {
+        let series = self.series.as_mut().ok_or_else(|| {
+            ClientError::Parse("Observation found outside of a series".to_string())
+        })?;
+
+        let metadata = match &series.metadata {
+            Some(metadata) => metadata,
+            None => {
+                let currency = series.values.get("CURRENCY").ok_or_else(|| {
+                    ClientError::Parse("CURRENCY dimension not found".to_string())
+                })?;
+
+                let metadata =
+                    SeriesMetadata::from_attributes(currency.clone(), None, &series.values);
+                self.data.series.push(metadata.clone());
+                series.metadata.insert(metadata)
+            }
+        };
+
+        let time_period = observation
+            .time_period
+            .ok_or_else(|| ClientError::Parse("TIME_PERIOD dimension not found".to_string()))?;
+
+        let observation_date = parse_time_period(&time_period)?;
+
+        // Observations without a value are kept, their status saying why.
+        let rate = observation
+            .value
+            .and_then(|value| value.parse::<f64>().ok())
+            .filter(|value| value.is_finite())
+            .map(|value| metadata.scale(value));
+
+        self.data.rates.push(ExchangeRate {
+            currency: metadata.currency.clone(),
+            rate,
+            observation_date,
+            status: observation
+                .attributes
+                .get("OBS_STATUS")
+                .map(|code| ObservationStatus::from_code(code))
+                .unwrap_or_default(),
+            confidentiality: observation
+                .attributes
+                .get("OBS_CONF")
+                .map(|code| ObservationConfidentiality::from_code(code)),
+        });
+
+        Ok(())
+    }
+}
+
+pub(crate) fn element_attributes(element: &BytesStart) -> Result<HashMap<String, String>> {
+    element
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute
+                .map_err(|e| ClientError::Parse(format!("Invalid SDMX-ML attribute: {}", e)))?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| ClientError::Parse(format!("Invalid SDMX-ML attribute value: {}", e)))?
+                .into_owned();
+            Ok((key, value))
+        })
+        .collect()
+}
@@ -0,0 +1,216 @@
+//! Typed description of the EXR series key (`FREQ.CURRENCY.EUR.SP00.SUFFIX`)
+//! and of the SDMX time periods it is observed over.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::error::{ClientError, Result};
+
+/// `FREQ` dimension of the EXR dataflow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Frequency {
+    #[default]
+    Daily,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+/// `EXR_SUFFIX` dimension of the EXR dataflow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SeriesVariation {
+    /// Average over the period, or the reference rate itself for daily series.
+    #[default]
+    Average,
+    /// Rate at the end of the period.
+    EndOfPeriod,
+}
+
+/// An EXR spot-rate series against the euro, for every quoted currency.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ExrSeries {
+    pub frequency: Frequency,
+    pub variation: SeriesVariation,
+}
+
+/// SDMX key of an EXR series, optionally restricted to some currencies:
+/// `ExrKey::daily().currencies(["USD", "GBP"])` is `D.USD+GBP.EUR.SP00.A`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ExrKey {
+    series: ExrSeries,
+    currencies: Vec<String>,
+}
+
+impl Frequency {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "D",
+            Frequency::Monthly => "M",
+            Frequency::Quarterly => "Q",
+            Frequency::Annual => "A",
+        }
+    }
+
+    /// Formats the period containing `date` the way SDMX expects it in
+    /// `startPeriod`/`endPeriod`, e.g. `2025-03`, `2025-Q1` or `2025`.
+    pub fn format_period(&self, date: NaiveDate) -> String {
+        match self {
+            Frequency::Daily => date.format("%Y-%m-%d").to_string(),
+            Frequency::Monthly => date.format("%Y-%m").to_string(),
+            Frequency::Quarterly => format!("{}-Q{}", date.year(), date.month0() / 3 + 1),
+            Frequency::Annual => date.year().to_string(),
+        }
+    }
+}
+
+impl SeriesVariation {
+    pub fn code(&self) -> &'static str {
+        match self {
+            SeriesVariation::Average => "A",
+            SeriesVariation::EndOfPeriod => "E",
+        }
+    }
+}
+
+impl ExrSeries {
+    /// Daily reference rates, the series published every TARGET business day.
+    pub fn daily() -> Self {
+        Self::default()
+    }
+
+    pub fn new(frequency: Frequency, variation: SeriesVariation) -> Self {
+        Self {
+            frequency,
+            variation,
+        }
+    }
+
+    /// SDMX key selecting this series for every currency, e.g.
+    /// `M..EUR.SP00.A`.
+    pub fn key(&self) -> String {
+        ExrKey::new(*self).key()
+    }
+}
+
+impl ExrKey {
+    /// Daily reference rates for every currency.
+    pub fn daily() -> Self {
+        Self::new(ExrSeries::daily())
+    }
+
+    pub fn new(series: ExrSeries) -> Self {
+        Self {
+            series,
+            currencies: Vec::new(),
+        }
+    }
+
+    /// Restricts the key to `currencies`, in the given order. An empty list
+    /// selects every currency.
+    pub fn currencies<I, C>(mut self, currencies: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        self.currencies = currencies
+            .into_iter()
+            .map(|currency| currency.into().to_ascii_uppercase())
+            .collect();
+        self
+    }
+
+    pub fn series(&self) -> ExrSeries {
+        self.series
+    }
+
+    pub fn currency_codes(&self) -> &[String] {
+        &self.currencies
+    }
+
+    /// SDMX key, e.g. `D.USD+GBP.EUR.SP00.A` or `M..EUR.SP00.A`.
+    pub fn key(&self) -> String {
+        format!(
+            "{}.{}.EUR.SP00.{}",
+            self.series.frequency.code(),
+            self.currencies.join("+"),
+            self.series.variation.code()
+        )
+    }
+
+    /// Rejects currency codes that would change the meaning of the key or of
+    /// the URL it is sent in.
+    pub(crate) fn validate(&self) -> Result<()> {
+        validate_codes("currency", &self.currencies)
+    }
+}
+
+/// Checks that dimension codes interpolated into an SDMX key are plain
+/// alphanumeric codes.
+pub(crate) fn validate_codes(dimension: &str, codes: &[String]) -> Result<()> {
+    match codes
+        .iter()
+        .find(|code| code.is_empty() || !code.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        Some(code) => Err(ClientError::InvalidRequest(format!(
+            "Invalid {} code {:?}",
+            dimension, code
+        ))),
+        None => Ok(()),
+    }
+}
+
+impl From<ExrSeries> for ExrKey {
+    fn from(series: ExrSeries) -> Self {
+        Self::new(series)
+    }
+}
+
+/// Rejects a `start`..`end` range, both inclusive, that ends before it starts.
+pub(crate) fn validate_period(start: NaiveDate, end: NaiveDate) -> Result<()> {
+    if start > end {
+        return Err(ClientError::InvalidRequest(format!(
+            "Start date {} is after end date {}",
+            start, end
+        )));
+    }
+
+    Ok(())
+}
+
+/// Parses an SDMX `TIME_PERIOD` into the first day of the period it denotes.
+/// Supports daily (`2025-03-28`), monthly (`2025-03`), quarterly (`2025-Q1`),
+/// half-yearly (`2025-S1`) and annual (`2025`) periods.
+pub(crate) fn parse_time_period(time_period: &str) -> Result<NaiveDate> {
+    let invalid = || ClientError::Parse(format!("Invalid time period {}", time_period));
+
+    if let Ok(date) = NaiveDate::parse_from_str(time_period, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let (year, rest) = match time_period.split_once('-') {
+        Some((year, rest)) => (year, Some(rest)),
+        None => (time_period, None),
+    };
+    let year = year.parse::<i32>().map_err(|_| invalid())?;
+
+    let month = match rest {
+        None => 1,
+        Some(rest) => {
+            if let Some(quarter) = rest.strip_prefix('Q') {
+                let quarter = quarter.parse::<u32>().map_err(|_| invalid())?;
+                quarter
+                    .checked_sub(1)
+                    .filter(|q| *q < 4)
+                    .ok_or_else(invalid)?
+                    * 3
+                    + 1
+            } else if let Some(half) = rest.strip_prefix('S') {
+                let half = half.parse::<u32>().map_err(|_| invalid())?;
+                half.checked_sub(1).filter(|h| *h < 2).ok_or_else(invalid)? * 6 + 1
+            } else {
+                rest.parse::<u32>().map_err(|_| invalid())?
+            }
+        }
+    };
+
+    NaiveDate::from_ymd_opt(year, month, 1).ok_or_else(invalid)
+}
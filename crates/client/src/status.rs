@@ -0,0 +1,118 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// `OBS_STATUS` of an observation, telling a regular fixing apart from
+/// provisional or estimated values. Serialised as its SDMX code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ObservationStatus {
+    /// `A`, a normal value.
+    #[default]
+    Normal,
+    /// `P`, a provisional value that may still be revised.
+    Provisional,
+    /// `E`, an estimated value.
+    Estimated,
+    /// `M`, missing; the data cannot exist.
+    Missing,
+    /// Any other code of the `CL_OBS_STATUS` codelist.
+    Other(String),
+}
+
+/// `OBS_CONF` of an observation. Serialised as its SDMX code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ObservationConfidentiality {
+    /// `F`, free for publication.
+    #[default]
+    Free,
+    /// `N`, not for publication, restricted for internal use only.
+    NotForPublication,
+    /// `C`, confidential statistical information.
+    Confidential,
+    /// Any other code of the `CL_OBS_CONF` codelist.
+    Other(String),
+}
+
+impl ObservationStatus {
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "A" => Self::Normal,
+            "P" => Self::Provisional,
+            "E" => Self::Estimated,
+            "M" => Self::Missing,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Normal => "A",
+            Self::Provisional => "P",
+            Self::Estimated => "E",
+            Self::Missing => "M",
+            Self::Other(code) => code,
+        }
+    }
+
+    pub fn is_normal(&self) -> bool {
+        matches!(self, Self::Normal)
+    }
+}
+
+impl ObservationConfidentiality {
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "F" => Self::Free,
+            "N" => Self::NotForPublication,
+            "C" => Self::Confidential,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        match self {
+            Self::Free => "F",
+            Self::NotForPublication => "N",
+            Self::Confidential => "C",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for ObservationStatus {
+    fn from(code: String) -> Self {
+        Self::from_code(&code)
+    }
+}
+
+impl From<ObservationStatus> for String {
+    fn from(status: ObservationStatus) -> Self {
+        status.code().to_string()
+    }
+}
+
+impl From<String> for ObservationConfidentiality {
+    fn from(code: String) -> Self {
+        Self::from_code(&code)
+    }
+}
+
+impl From<ObservationConfidentiality> for String {
+    fn from(confidentiality: ObservationConfidentiality) -> Self {
+        confidentiality.code().to_string()
+    }
+}
+
+impl fmt::Display for ObservationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Provisional => write!(f, "provisional"),
+            Self::Estimated => write!(f, "estimated"),
+            Self::Missing => write!(f, "missing"),
+            Self::Other(code) => write!(f, "status {}", code),
+        }
+    }
+}
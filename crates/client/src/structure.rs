@@ -0,0 +1,276 @@
+//! Decoder for SDMX-ML 2.1 structure messages, as returned by the `dataflow`
+//! and `codelist` endpoints, telling which dataflows, currencies and series
+//! variations the ECB publishes.
+
+use quick_xml::{
+    Reader,
+    escape::resolve_predefined_entity,
+    events::{BytesStart, Event},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BceClient,
+    error::{ClientError, Result},
+    http,
+    sdmx_xml::element_attributes,
+};
+
+/// Agency maintaining the structures the ECB data API serves.
+const AGENCY: &str = "ECB";
+
+/// A dataflow, e.g. `EXR` "Exchange Rates".
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Dataflow {
+    pub id: String,
+    pub agency_id: String,
+    pub version: String,
+    /// English name, or the first name given.
+    pub name: Option<String>,
+    /// Id of the data structure definition the dataflow uses, e.g.
+    /// `ECB_EXR1`.
+    pub structure_id: Option<String>,
+}
+
+/// A codelist, e.g. `CL_CURRENCY`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Codelist {
+    pub id: String,
+    pub agency_id: String,
+    pub version: String,
+    /// English name, or the first name given.
+    pub name: Option<String>,
+    pub codes: Vec<Code>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Code {
+    pub id: String,
+    /// English name, or the first name given, e.g. "US dollar".
+    pub name: Option<String>,
+}
+
+/// Dataflows and codelists of a structure message.
+#[derive(Debug, Clone, Default)]
+pub struct Structures {
+    pub dataflows: Vec<Dataflow>,
+    pub codelists: Vec<Codelist>,
+}
+
+impl Codelist {
+    pub fn code(&self, id: &str) -> Option<&Code> {
+        self.codes.iter().find(|code| code.id == id)
+    }
+}
+
+impl BceClient {
+    /// Fetches every dataflow the ECB publishes.
+    pub async fn fetch_dataflows(&self) -> Result<Vec<Dataflow>> {
+        Ok(self.fetch_structures("dataflow", None).await?.dataflows)
+    }
+
+    /// Fetches the latest version of the dataflow `id`, e.g. `EXR`.
+    pub async fn fetch_dataflow(&self, id: &str) -> Result<Dataflow> {
+        self.fetch_structures("dataflow", Some(id))
+            .await?
+            .dataflows
+            .into_iter()
+            .find(|dataflow| dataflow.id == id)
+            .ok_or(ClientError::NoData)
+    }
+
+    /// Fetches the latest version of the codelist `id`, e.g. `CL_CURRENCY` or
+    /// `CL_EXR_SUFFIX`.
+    pub async fn fetch_codelist(&self, id: &str) -> Result<Codelist> {
+        self.fetch_structures("codelist", Some(id))
+            .await?
+            .codelists
+            .into_iter()
+            .find(|codelist| codelist.id == id)
+            .ok_or(ClientError::NoData)
+    }
+
+    async fn fetch_structures(&self, resource: &str, id: Option<&str>) -> Result<Structures> {
+        let mut url = format!("{}/{}/{}", self.config.base_url, resource, AGENCY);
+        if let Some(id) = id {
+            if id.is_empty() || !id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') {
+                return Err(ClientError::InvalidRequest(format!(
+                    "Invalid {} id {:?}",
+                    resource, id
+                )));
+            }
+            url.push_str(&format!("/{}/latest", id));
+        }
+
+        let body = http::get(
+            self.http_client.as_ref(),
+            &url,
+            &self.config,
+            &self.retry_policy,
+        )
+        .await?
+        .ok_or(ClientError::NoData)?;
+
+        parse_structures(&http::body_to_string(body)?)
+    }
+}
+
+/// Name being read and the language it is given in.
+struct NameState {
+    lang: Option<String>,
+    text: String,
+}
+
+#[derive(Default)]
+struct DecoderState {
+    dataflow: Option<Dataflow>,
+    codelist: Option<Codelist>,
+    code: Option<Code>,
+    name: Option<NameState>,
+    in_structure_reference: bool,
+    structures: Structures,
+}
+
+/// Decodes the dataflows and codelists of an SDMX-ML 2.1 structure message.
+pub fn parse_structures(body: &str) -> Result<Structures> {
+    let mut reader = Reader::from_str(body);
+
+    let mut state = DecoderState::default();
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            ClientError::Parse(format!(
+                "Invalid SDMX-ML at {}: {}",
+                reader.buffer_position(),
+                e
+            ))
+        })?;
+
+        match event {
+            Event::Start(element) => state.start_element(&element)?,
+            Event::Empty(element) => {
+                state.start_element(&element)?;
+                state.end_element(element.local_name().as_ref());
+            }
+            Event::End(element) => state.end_element(element.local_name().as_ref()),
+            Event::Text(text) => {
+                if let Some(name) = state.name.as_mut() {
+                    let text = text
+                        .decode()
+                        .map_err(|e| ClientError::Parse(format!("Invalid SDMX-ML text: {}", e)))?;
+                    name.text.push_str(&text);
+                }
+            }
+            Event::GeneralRef(reference) => {
+                if let Some(name) = state.name.as_mut() {
+                    let invalid = |e: &dyn std::fmt::Display| {
+                        ClientError::Parse(format!("Invalid SDMX-ML reference: {}", e))
+                    };
+
+                    match reference.resolve_char_ref().map_err(|e| invalid(&e))? {
+                        Some(ch) => name.text.push(ch),
+                        None => {
+                            let entity = reference.decode().map_err(|e| invalid(&e))?;
+                            let resolved = resolve_predefined_entity(&entity)
+                                .ok_or_else(|| invalid(&entity))?;
+                            name.text.push_str(resolved);
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(state.structures)
+}
+
+impl DecoderState {
+    fn start_element(&mut self, element: &BytesStart) -> Result<()> {
+        match element.local_name().as_ref() {
+            b"Dataflow" => {
+                let mut attributes = element_attributes(element)?;
+                self.dataflow = Some(Dataflow {
+                    id: attributes.remove("id").unwrap_or_default(),
+                    agency_id: attributes.remove("agencyID").unwrap_or_default(),
+                    version: attributes.remove("version").unwrap_or_default(),
+                    name: None,
+                    structure_id: None,
+                });
+            }
+            b"Codelist" => {
+                let mut attributes = element_attributes(element)?;
+                self.codelist = Some(Codelist {
+                    id: attributes.remove("id").unwrap_or_default(),
+                    agency_id: attributes.remove("agencyID").unwrap_or_default(),
+                    version: attributes.remove("version").unwrap_or_default(),
+                    name: None,
+                    codes: Vec::new(),
+                });
+            }
+            b"Code" if self.codelist.is_some() => {
+                self.code = Some(Code {
+                    id: element_attributes(element)?
+                        .remove("id")
+                        .unwrap_or_default(),
+                    name: None,
+                });
+            }
+            b"Name" if self.dataflow.is_some() || self.codelist.is_some() => {
+                self.name = Some(NameState {
+                    lang: element_attributes(element)?.remove("lang"),
+                    text: String::new(),
+                });
+            }
+            b"Structure" if self.dataflow.is_some() => self.in_structure_reference = true,
+            b"Ref" if self.in_structure_reference => {
+                if let Some(dataflow) = self.dataflow.as_mut() {
+                    dataflow.structure_id = element_attributes(element)?.remove("id");
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn end_element(&mut self, local_name: &[u8]) {
+        match local_name {
+            b"Name" => {
+                if let Some(name) = self.name.take() {
+                    let target = if let Some(code) = self.code.as_mut() {
+                        &mut code.name
+                    } else if let Some(codelist) = self.codelist.as_mut() {
+                        &mut codelist.name
+                    } else if let Some(dataflow) = self.dataflow.as_mut() {
+                        &mut dataflow.name
+                    } else {
+                        return;
+                    };
+
+                    if target.is_none() || name.lang.as_deref() == Some("en") {
+                        *target = Some(name.text.trim().to_string());
+                    }
+                }
+            }
+            b"Code" => {
+                if let (Some(code), Some(codelist)) = (self.code.take(), self.codelist.as_mut()) {
+                    codelist.codes.push(code);
+                }
+            }
+            b"Codelist" => {
+                if let Some(codelist) = self.codelist.take() {
+                    self.structures.codelists.push(codelist);
+                }
+            }
+            b"Structure" => self.in_structure_reference = false,
+            b"Dataflow" => {
+                if let Some(dataflow) = self.dataflow.take() {
+                    self.structures.dataflows.push(dataflow);
+                }
+            }
+            _ => {}
+        }
+    }
+}
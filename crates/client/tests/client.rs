@@ -0,0 +1,526 @@
+use std::sync::Arc;
+
+use bce_exchange_client::{
+    BceClient, BceClientConfig, CacheValidators, ClientError, Conditional, DataFormat,
+    ExchangeRatesSnapshot, ExrKey, ObservationStatus,
+};
+use bce_exchange_test_support::{DATA_API, MockHttpClient, MockResponse, client, date, fixtures};
+
+#[tokio::test]
+async fn decodes_the_latest_fixing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let snapshot = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.observation_date, date(2025, 3, 28));
+    let currencies = snapshot
+        .rates
+        .iter()
+        .map(|rate| rate.currency.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(currencies, ["CHF", "GBP", "JPY", "USD"]);
+
+    let usd = snapshot
+        .rates
+        .iter()
+        .find(|rate| rate.currency == "USD")
+        .unwrap();
+    assert_eq!(usd.rate, Some(1.0807));
+    assert!(usd.status.is_normal());
+
+    let jpy = snapshot.series_metadata("JPY").unwrap();
+    assert_eq!(jpy.currency_name.as_deref(), Some("Japanese yen"));
+    assert_eq!(jpy.decimals, Some(2));
+
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(
+        requests[0]
+            .url
+            .starts_with(&format!("{}D..EUR.SP00.A?", DATA_API))
+    );
+    assert!(requests[0].url.contains("lastNObservations=1"));
+}
+
+#[tokio::test]
+async fn keeps_observations_without_a_value() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::missing_observation());
+
+    let snapshots = client(&http_client)
+        .fetch_series_between(ExrKey::daily(), date(2025, 3, 24), date(2025, 3, 28))
+        .await
+        .unwrap();
+
+    let friday = snapshots.last().unwrap();
+    assert_eq!(friday.observation_date, date(2025, 3, 28));
+    let jpy = friday
+        .rates
+        .iter()
+        .find(|rate| rate.currency == "JPY")
+        .unwrap();
+    assert_eq!(jpy.rate, None);
+    assert_eq!(jpy.status, ObservationStatus::Missing);
+
+    let csv = DataFormat::CsvData
+        .parse_exchange_rates(fixtures::EXR_MISSING_OBSERVATION_CSV)
+        .unwrap();
+    let jpy = csv
+        .rates
+        .iter()
+        .find(|rate| rate.currency == "JPY")
+        .unwrap();
+    assert_eq!(jpy.rate, None);
+    assert_eq!(jpy.status, ObservationStatus::Missing);
+    assert_eq!(csv.rates.len(), 2);
+}
+
+async fn assert_decodes_xml(format: DataFormat, response: MockResponse, query_value: &str) {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, response);
+
+    let snapshots = client(&http_client)
+        .with_format(format)
+        .fetch_series_between(ExrKey::daily(), date(2025, 3, 27), date(2025, 3, 28))
+        .await
+        .unwrap();
+
+    assert!(
+        http_client.requests()[0]
+            .url
+            .contains(&format!("format={}&", query_value))
+    );
+
+    let [thursday, friday] = snapshots.as_slice() else {
+        panic!("expected two fixings, got {snapshots:?}");
+    };
+
+    assert_eq!(thursday.observation_date, date(2025, 3, 27));
+    let usd = &thursday.rates[1];
+    assert_eq!((usd.currency.as_str(), usd.rate), ("USD", None));
+    assert_eq!(usd.status, ObservationStatus::Missing);
+
+    assert_eq!(friday.observation_date, date(2025, 3, 28));
+    let rates = friday
+        .rates
+        .iter()
+        .map(|rate| (rate.currency.as_str(), rate.rate))
+        .collect::<Vec<_>>();
+    assert_eq!(rates, [("JPY", Some(162.55)), ("USD", Some(1.0807))]);
+    assert_eq!(friday.rates[1].status, ObservationStatus::Provisional);
+
+    let jpy = friday.series_metadata("JPY").unwrap();
+    assert_eq!(jpy.decimals, Some(2));
+    assert_eq!(jpy.title.as_deref(), Some("Japanese yen/Euro"));
+}
+
+#[tokio::test]
+async fn decodes_generic_xml() {
+    assert_decodes_xml(
+        DataFormat::GenericXml,
+        fixtures::generic_xml(),
+        "genericdata",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn decodes_structure_specific_xml() {
+    assert_decodes_xml(
+        DataFormat::StructureSpecificXml,
+        fixtures::structure_specific_xml(),
+        "structurespecificdata",
+    )
+    .await;
+}
+
+#[test]
+fn csv_and_json_decode_to_the_same_snapshot() {
+    let snapshot = |format: DataFormat, body: &str| {
+        let data = format.parse_exchange_rates(body).unwrap();
+        ExchangeRatesSnapshot::from_rates(data.rates, String::new())
+            .unwrap()
+            .with_series_metadata(&data.series)
+    };
+    let json = snapshot(DataFormat::JsonData, fixtures::EXR_DAILY);
+    let csv = snapshot(DataFormat::CsvData, fixtures::EXR_DAILY_CSV);
+
+    assert_eq!(csv.observation_date, json.observation_date);
+
+    let observations = |snapshot: &ExchangeRatesSnapshot| {
+        snapshot
+            .rates
+            .iter()
+            .map(|rate| {
+                (
+                    rate.currency.clone(),
+                    rate.rate,
+                    rate.observation_date,
+                    rate.status.clone(),
+                    rate.confidentiality.clone(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(observations(&csv), observations(&json));
+    assert_eq!(json.rates.len(), 4);
+
+    // Only SDMX-JSON names the currencies.
+    let mut series = json.series;
+    for metadata in &mut series {
+        assert!(metadata.currency_name.take().is_some());
+    }
+    series.sort_by(|a, b| a.currency.cmp(&b.currency));
+    let mut csv_series = csv.series;
+    csv_series.sort_by(|a, b| a.currency.cmp(&b.currency));
+    assert_eq!(csv_series, series);
+}
+
+#[tokio::test]
+async fn weekend_has_no_fixing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::weekend());
+
+    let snapshots = client(&http_client)
+        .fetch_exchange_rates_between(date(2025, 3, 29), date(2025, 3, 30))
+        .await
+        .unwrap();
+
+    assert!(snapshots.is_empty());
+    assert!(
+        http_client.requests()[0]
+            .url
+            .contains("startPeriod=2025-03-29&endPeriod=2025-03-30")
+    );
+}
+
+#[tokio::test]
+async fn empty_dataset_is_no_data() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::empty_dataset());
+
+    let error = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::NoData), "{error:?}");
+}
+
+#[tokio::test]
+async fn missing_currency_dimension_is_a_parse_error() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::missing_currency_dimension());
+
+    let error = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, ClientError::Parse(message) if message.contains("CURRENCY")),
+        "{error:?}"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn server_errors_are_retried_until_attempts_run_out() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::server_error());
+
+    let error = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ClientError::HttpStatus { status: 500, .. }),
+        "{error:?}"
+    );
+    assert_eq!(http_client.requests().len(), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn server_error_recovers_on_retry() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::server_error())
+        .respond(DATA_API, fixtures::normal_day());
+
+    let snapshot = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap();
+
+    assert_eq!(snapshot.rates.len(), 4);
+    assert_eq!(http_client.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn client_errors_are_not_retried() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, MockResponse::new(400, "Bad request"));
+
+    let error = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ClientError::HttpStatus { status: 400, .. }),
+        "{error:?}"
+    );
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retry_after_beyond_max_backoff_is_not_waited_for() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(
+        DATA_API,
+        MockResponse::new(429, "Too many requests").with_header("Retry-After", "3600"),
+    );
+
+    let error = client(&http_client)
+        .fetch_all_exchange_rates()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ClientError::HttpStatus { status: 429, .. }),
+        "{error:?}"
+    );
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn conditional_request_sends_validators() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(
+            DATA_API,
+            fixtures::normal_day()
+                .with_header("ETag", "\"exr-20250328\"")
+                .with_header("Last-Modified", "Fri, 28 Mar 2025 15:02:41 GMT"),
+        )
+        .respond(DATA_API, MockResponse::new(304, ""));
+    let client = client(&http_client);
+
+    let Conditional::Modified { validators, .. } = client
+        .fetch_all_exchange_rates_if_modified(&CacheValidators::default())
+        .await
+        .unwrap()
+    else {
+        panic!("first request should download the rates");
+    };
+    assert_eq!(validators.etag.as_deref(), Some("\"exr-20250328\""));
+
+    let refreshed = client
+        .fetch_all_exchange_rates_if_modified(&validators)
+        .await
+        .unwrap();
+    assert!(matches!(refreshed, Conditional::NotModified));
+
+    let requests = http_client.requests();
+    assert_eq!(requests[0].header("If-None-Match"), None);
+    assert_eq!(
+        requests[1].header("If-None-Match"),
+        Some("\"exr-20250328\"")
+    );
+    assert_eq!(
+        requests[1].header("If-Modified-Since"),
+        Some("Fri, 28 Mar 2025 15:02:41 GMT")
+    );
+}
+
+#[tokio::test]
+async fn config_sets_endpoint_and_headers() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(
+        "http://localhost:8080/ecb/data/EXR/",
+        fixtures::normal_day(),
+    );
+
+    let client = BceClient::new(Arc::new(http_client.clone())).with_config(
+        BceClientConfig::builder()
+            .base_url("http://localhost:8080/ecb/")
+            .user_agent("treasury-agent/2.0")
+            .header("X-Proxy-Token", "secret")
+            .build(),
+    );
+
+    client.fetch_all_exchange_rates().await.unwrap();
+
+    let request = &http_client.requests()[0];
+    assert_eq!(request.header("User-Agent"), Some("treasury-agent/2.0"));
+    assert_eq!(request.header("X-Proxy-Token"), Some("secret"));
+}
+
+#[tokio::test]
+async fn key_restricts_currencies() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    client(&http_client)
+        .fetch_latest_exchange_rates(ExrKey::daily().currencies(["USD", "GBP"]))
+        .await
+        .unwrap();
+
+    assert!(
+        http_client.requests()[0]
+            .url
+            .starts_with(&format!("{}D.USD+GBP.EUR.SP00.A?", DATA_API))
+    );
+}
+
+#[tokio::test]
+async fn invalid_currency_code_is_rejected_before_sending() {
+    let http_client = MockHttpClient::new();
+
+    let error = client(&http_client)
+        .fetch_latest_exchange_rates(ExrKey::daily().currencies(["USD/../X"]))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::InvalidRequest(_)), "{error:?}");
+    assert!(http_client.requests().is_empty());
+}
+
+#[tokio::test]
+async fn streams_history_one_series_at_a_time() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::history_csv());
+
+    let mut stream = client(&http_client)
+        .stream_series_between(ExrKey::daily(), date(2025, 3, 26), date(2025, 3, 28))
+        .await
+        .unwrap();
+
+    let jpy = stream.next_series().await.unwrap().unwrap();
+    assert_eq!(jpy.metadata.currency, "JPY");
+    assert_eq!(jpy.metadata.decimals, Some(2));
+    assert_eq!(
+        jpy.rates.iter().map(|rate| rate.rate).collect::<Vec<_>>(),
+        [Some(162.92), Some(163.13), Some(162.55)]
+    );
+
+    let usd = stream.next_series().await.unwrap().unwrap();
+    assert_eq!(usd.metadata.currency, "USD");
+    assert_eq!(usd.rates.len(), 3);
+    assert_eq!(usd.rates[2].observation_date, date(2025, 3, 28));
+    assert!(!usd.rates[2].status.is_normal());
+
+    assert!(stream.next_series().await.unwrap().is_none());
+    assert!(http_client.requests()[0].url.contains("format=csvdata"));
+}
+
+#[tokio::test]
+async fn streaming_a_period_without_fixings_yields_nothing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::weekend());
+
+    let mut stream = client(&http_client)
+        .stream_series_between(ExrKey::daily(), date(2025, 3, 29), date(2025, 3, 30))
+        .await
+        .unwrap();
+
+    assert!(stream.next_series().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn lists_dataflows() {
+    let http_client = MockHttpClient::new();
+    http_client.respond("/dataflow/ECB", fixtures::dataflows());
+
+    let dataflows = client(&http_client).fetch_dataflows().await.unwrap();
+
+    assert_eq!(
+        dataflows
+            .iter()
+            .map(|dataflow| dataflow.id.as_str())
+            .collect::<Vec<_>>(),
+        ["EST", "EXR", "ICP"]
+    );
+    assert_eq!(dataflows[1].name.as_deref(), Some("Exchange Rates"));
+    assert_eq!(dataflows[1].structure_id.as_deref(), Some("ECB_EXR1"));
+    assert_eq!(
+        http_client.requests()[0].url,
+        "https://data-api.ecb.europa.eu/service/dataflow/ECB"
+    );
+}
+
+#[tokio::test]
+async fn decodes_currency_codelist() {
+    let http_client = MockHttpClient::new();
+    http_client.respond("/codelist/ECB/CL_CURRENCY", fixtures::codelist_currency());
+
+    let codelist = client(&http_client)
+        .fetch_codelist("CL_CURRENCY")
+        .await
+        .unwrap();
+
+    assert_eq!(codelist.agency_id, "ECB");
+    assert_eq!(codelist.name.as_deref(), Some("Currency code list"));
+    assert_eq!(codelist.codes.len(), 8);
+    assert_eq!(
+        codelist.code("CHF").unwrap().name.as_deref(),
+        Some("Swiss franc")
+    );
+    assert_eq!(
+        codelist.code("STN").unwrap().name.as_deref(),
+        Some("São Tomé and Príncipe dobra")
+    );
+    assert_eq!(
+        codelist.code("TTD").unwrap().name.as_deref(),
+        Some("Trinidad & Tobago dollar")
+    );
+    assert!(
+        http_client.requests()[0]
+            .url
+            .ends_with("/codelist/ECB/CL_CURRENCY/latest")
+    );
+}
+
+#[tokio::test]
+async fn decodes_series_variation_codelist() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(
+        "/codelist/ECB/CL_EXR_SUFFIX",
+        fixtures::codelist_exr_suffix(),
+    );
+
+    let codelist = client(&http_client)
+        .fetch_codelist("CL_EXR_SUFFIX")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        codelist
+            .codes
+            .iter()
+            .map(|code| code.id.as_str())
+            .collect::<Vec<_>>(),
+        ["A", "E", "P"]
+    );
+    assert_eq!(
+        codelist.code("E").unwrap().name.as_deref(),
+        Some("End-of-period")
+    );
+}
+
+#[tokio::test]
+async fn invalid_structure_id_is_rejected_before_sending() {
+    let http_client = MockHttpClient::new();
+
+    let error = client(&http_client)
+        .fetch_codelist("CL_CURRENCY/../X")
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::InvalidRequest(_)), "{error:?}");
+    assert!(http_client.requests().is_empty());
+}
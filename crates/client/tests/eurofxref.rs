@@ -0,0 +1,135 @@
+use bce_exchange_client::{
+    ClientError, ExchangeRatesSnapshot,
+    eurofxref::{extract_history_csv, parse_csv},
+};
+use bce_exchange_test_support::{EUROFXREF, MockHttpClient, date, eurofxref_client, fixtures};
+
+fn rates(snapshot: &ExchangeRatesSnapshot) -> Vec<(&str, Option<f64>)> {
+    snapshot
+        .rates
+        .iter()
+        .map(|rate| (rate.currency.as_str(), rate.rate))
+        .collect()
+}
+
+#[tokio::test]
+async fn fetches_the_daily_fixing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(EUROFXREF, fixtures::eurofxref_daily());
+
+    let snapshot = eurofxref_client(&http_client).fetch_daily().await.unwrap();
+
+    assert_eq!(snapshot.observation_date, date(2025, 3, 28));
+    assert_eq!(
+        rates(&snapshot),
+        [
+            ("CHF", Some(0.953)),
+            ("GBP", Some(0.8365)),
+            ("JPY", Some(162.55)),
+            ("USD", Some(1.0807)),
+        ]
+    );
+    assert_eq!(
+        http_client.requests()[0].url,
+        format!("{}eurofxref-daily.xml", EUROFXREF)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn daily_fixing_reports_server_errors() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(EUROFXREF, fixtures::server_error());
+
+    let error = eurofxref_client(&http_client)
+        .fetch_daily()
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ClientError::HttpStatus { status: 500, .. }),
+        "{error:?}"
+    );
+    assert_eq!(http_client.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn fetches_the_last_90_days() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(EUROFXREF, fixtures::eurofxref_hist_90d());
+
+    let snapshots = eurofxref_client(&http_client)
+        .fetch_last_90_days()
+        .await
+        .unwrap();
+
+    let dates = snapshots
+        .iter()
+        .map(|snapshot| snapshot.observation_date)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        dates,
+        [date(2025, 3, 26), date(2025, 3, 27), date(2025, 3, 28)]
+    );
+    assert_eq!(
+        rates(&snapshots[0]),
+        [("JPY", Some(162.92)), ("USD", Some(1.0773))]
+    );
+    assert_eq!(
+        http_client.requests()[0].url,
+        format!("{}eurofxref-hist-90d.xml", EUROFXREF)
+    );
+}
+
+#[tokio::test]
+async fn fetches_the_history_archive() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(EUROFXREF, fixtures::eurofxref_hist_zip());
+
+    let snapshots = eurofxref_client(&http_client)
+        .fetch_history()
+        .await
+        .unwrap();
+
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(snapshots[2].observation_date, date(2025, 3, 28));
+    assert_eq!(
+        http_client.requests()[0].url,
+        format!("{}eurofxref-hist.zip", EUROFXREF)
+    );
+}
+
+#[test]
+fn extracts_the_history_csv() {
+    let csv = extract_history_csv(fixtures::EUROFXREF_HIST_ZIP).unwrap();
+
+    assert_eq!(csv, fixtures::EUROFXREF_HIST_CSV);
+}
+
+#[test]
+fn invalid_archive_is_a_parse_error() {
+    let error = extract_history_csv(fixtures::EUROFXREF_HIST_CSV.as_bytes()).unwrap_err();
+
+    assert!(matches!(error, ClientError::Parse(_)), "{error:?}");
+}
+
+#[test]
+fn history_csv_skips_unquoted_currencies() {
+    let snapshots = parse_csv(fixtures::EUROFXREF_HIST_CSV).unwrap();
+
+    // Sorted by date, although the file lists the newest first.
+    assert_eq!(snapshots[0].observation_date, date(1999, 1, 4));
+    assert_eq!(
+        rates(&snapshots[0]),
+        [
+            ("CYP", Some(0.58231)),
+            ("JPY", Some(133.73)),
+            ("USD", Some(1.1789)),
+        ]
+    );
+
+    assert_eq!(snapshots[2].observation_date, date(2025, 3, 28));
+    assert_eq!(
+        rates(&snapshots[2]),
+        [("JPY", Some(162.55)), ("USD", Some(1.0807))]
+    );
+}
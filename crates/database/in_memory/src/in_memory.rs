@@ -1,20 +1,29 @@
 use std::{collections::HashMap, sync::Arc};
 
-use anyhow::Result;
 use async_trait::async_trait;
-use bce_exchange_client::ExchangeRatesSnapshot;
-use bce_exchange_database::{ExchangeRateRecord, StorageAdapter};
+use bce_exchange_client::{Codelist, ExchangeRatesSnapshot};
+use bce_exchange_database::{ExchangeRateRecord, Result, StorageAdapter};
 use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 
 pub struct InMemoryStorageAdapter {
     pub(crate) cache: Arc<RwLock<HashMap<String, ExchangeRateRecord>>>,
+    pub(crate) syncs: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+    pub(crate) codelists: Arc<RwLock<HashMap<String, Codelist>>>,
+}
+
+impl Default for InMemoryStorageAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InMemoryStorageAdapter {
     pub fn new() -> Self {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
+            syncs: Arc::new(RwLock::new(HashMap::new())),
+            codelists: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -56,11 +65,60 @@ impl StorageAdapter for InMemoryStorageAdapter {
         Ok(cache.get(source_identifier).cloned())
     }
 
+    async fn get_newest_exchange_rates(&self, prefix: &str) -> Result<Option<ExchangeRateRecord>> {
+        let cache = self.cache.read().await;
+        Ok(cache
+            .values()
+            .filter(|record| record.source_identifier.starts_with(prefix))
+            .max_by_key(|record| {
+                (
+                    record.snapshot.observation_date,
+                    record.snapshot.rates.len(),
+                    record.fetch_timestamp,
+                )
+            })
+            .cloned())
+    }
+
+    async fn touch_exchange_rates(
+        &self,
+        source_identifier: &str,
+        fetch_timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut cache = self.cache.write().await;
+        if let Some(record) = cache.get_mut(source_identifier) {
+            record.fetch_timestamp = fetch_timestamp;
+        }
+        Ok(())
+    }
+
     async fn exchange_rates_exist(&self, source_identifier: &str) -> Result<bool> {
         let cache = self.cache.read().await;
         Ok(cache.contains_key(source_identifier))
     }
 
+    async fn get_last_sync(&self, sync_identifier: &str) -> Result<Option<DateTime<Utc>>> {
+        let syncs = self.syncs.read().await;
+        Ok(syncs.get(sync_identifier).copied())
+    }
+
+    async fn store_last_sync(&self, sync_identifier: &str, synced_at: DateTime<Utc>) -> Result<()> {
+        let mut syncs = self.syncs.write().await;
+        syncs.insert(sync_identifier.to_string(), synced_at);
+        Ok(())
+    }
+
+    async fn store_codelist(&self, codelist: &Codelist) -> Result<()> {
+        let mut codelists = self.codelists.write().await;
+        codelists.insert(codelist.id.clone(), codelist.clone());
+        Ok(())
+    }
+
+    async fn get_codelist(&self, codelist_id: &str) -> Result<Option<Codelist>> {
+        let codelists = self.codelists.read().await;
+        Ok(codelists.get(codelist_id).cloned())
+    }
+
     async fn health_check(&self) -> Result<()> {
         // For in-memory storage, always healthy
         Ok(())
@@ -0,0 +1,4 @@
+CREATE TABLE sync_state (
+    sync_identifier TEXT PRIMARY KEY,
+    synced_at INTEGER NOT NULL
+);
@@ -0,0 +1,4 @@
+CREATE TABLE codelists (
+    codelist_id TEXT PRIMARY KEY,
+    codelist_json TEXT NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Each source identifier holds a single record, replaced on every store: drop
+-- the ones a later fetch superseded.
+DELETE FROM exchange_rates
+WHERE EXISTS (
+    SELECT 1 FROM exchange_rates AS newer
+    WHERE newer.source_identifier = exchange_rates.source_identifier
+      AND newer.fetch_time > exchange_rates.fetch_time
+);
+
+DROP INDEX idx_source_fetch_time;
+CREATE UNIQUE INDEX idx_source_identifier ON exchange_rates(source_identifier);
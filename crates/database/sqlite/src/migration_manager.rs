@@ -1,4 +1,4 @@
-use anyhow::Result;
+use bce_exchange_database::{Result, StorageError};
 use sqlx::sqlite::SqlitePool;
 
 pub struct MigrationManager;
@@ -28,7 +28,7 @@ impl MigrationManager {
         )
         .execute(pool)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to create version table: {}", e))?;
+        .map_err(|e| StorageError::storage("Failed to create version table", e))?;
 
         Ok(())
     }
@@ -37,7 +37,7 @@ impl MigrationManager {
         let version: Option<i32> = sqlx::query_scalar("SELECT MAX(version) FROM schema_version")
             .fetch_optional(pool)
             .await
-            .map_err(|e| anyhow::anyhow!("Database operation failed: {}", e))?
+            .map_err(|e| StorageError::storage("Database operation failed", e))?
             .flatten();
 
         Ok(version.unwrap_or(0))
@@ -58,24 +58,25 @@ impl MigrationManager {
     }
 
     async fn apply_migration(pool: &SqlitePool, version: i32, migration: &Migration) -> Result<()> {
-        let mut tx = pool.begin().await.map_err(anyhow::Error::from)?;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| StorageError::migration(version, e))?;
 
         sqlx::query(migration.sql)
             .execute(&mut *tx)
             .await
-            .map_err(|e| anyhow::anyhow!("Migration {} failed: {}", version, e))?;
+            .map_err(|e| StorageError::migration(version, e))?;
 
         sqlx::query("INSERT INTO schema_version (version) VALUES (?)")
             .bind(version)
             .execute(&mut *tx)
             .await
-            .map_err(|e| {
-                anyhow::anyhow!("Failed to record migration version {}: {}", version, e)
-            })?;
+            .map_err(|e| StorageError::migration(version, e))?;
 
         tx.commit()
             .await
-            .map_err(|e| anyhow::anyhow!("Operation failed: {}", e))?;
+            .map_err(|e| StorageError::migration(version, e))?;
 
         Ok(())
     }
@@ -87,10 +88,33 @@ struct Migration {
     sql: &'static str,
 }
 
-static MIGRATIONS: &[Migration] = &[Migration {
-    description: "Initial schema - exchange rates table",
-    sql: include_str!(concat!(
-        env!("CARGO_MANIFEST_DIR"),
-        "/migrations/20250810162329_initial_schema.sql"
-    )),
-}];
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        description: "Initial schema - exchange rates table",
+        sql: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/migrations/20250810162329_initial_schema.sql"
+        )),
+    },
+    Migration {
+        description: "Sync state table",
+        sql: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/migrations/20261017090000_sync_state.sql"
+        )),
+    },
+    Migration {
+        description: "Codelists table",
+        sql: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/migrations/20261017120000_codelists.sql"
+        )),
+    },
+    Migration {
+        description: "One exchange rates record per source identifier",
+        sql: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/migrations/20261017150000_one_record_per_source.sql"
+        )),
+    },
+];
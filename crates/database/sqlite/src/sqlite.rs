@@ -2,14 +2,14 @@ mod migration_manager;
 
 use std::collections::HashMap;
 
-use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use bce_exchange_database::{ExchangeRateRecord, StorageAdapter};
-use chrono::DateTime;
+use bce_exchange_client::Codelist;
+use bce_exchange_database::{ExchangeRateRecord, Result, StorageAdapter, StorageError};
+use chrono::{DateTime, Utc};
 use sqlx::{
     Row, Sqlite,
     pool::PoolConnection,
-    sqlite::{SqliteConnectOptions, SqlitePool},
+    sqlite::{SqliteConnectOptions, SqlitePool, SqliteRow},
 };
 
 use crate::migration_manager::MigrationManager;
@@ -26,12 +26,12 @@ impl SqliteStorageAdapter {
                 .create_if_missing(true),
         )
         .await
-        .map_err(|e| anyhow!("Connection error: {}", e))?;
+        .map_err(|e| StorageError::storage("Connection error", e))?;
 
         let mut conn = pool
             .acquire()
             .await
-            .map_err(|e| anyhow!("Connection error: {}", e))?;
+            .map_err(|e| StorageError::storage("Connection error", e))?;
 
         Self::apply_pragma_optimisations(&mut conn).await?;
         MigrationManager::ensure_current_schema(&pool).await?;
@@ -43,7 +43,7 @@ impl SqliteStorageAdapter {
         sqlx::query("PRAGMA journal_mode = WAL")
             .execute(&mut **conn)
             .await
-            .map_err(|e| anyhow!("Operation failed: {}", e))?;
+            .map_err(|e| StorageError::storage("Operation failed", e))?;
 
         let pragmas = [
             "PRAGMA synchronous = NORMAL",
@@ -58,7 +58,7 @@ impl SqliteStorageAdapter {
             sqlx::query(pragma)
                 .execute(&mut **conn)
                 .await
-                .map_err(|e| anyhow!("Operation failed: {}", e))?;
+                .map_err(|e| StorageError::storage("Operation failed", e))?;
         }
 
         Ok(())
@@ -69,14 +69,14 @@ impl SqliteStorageAdapter {
 impl StorageAdapter for SqliteStorageAdapter {
     async fn store_exchange_rates(&self, record: ExchangeRateRecord) -> Result<()> {
         let snapshot_json = serde_json::to_string(&record.snapshot)
-            .map_err(|e| anyhow!("Serialization error: {}", e))?;
+            .map_err(|e| StorageError::storage("Serialization error", e))?;
 
         let metadata_json = if record.metadata.is_empty() {
             None
         } else {
             Some(
                 serde_json::to_string(&record.metadata)
-                    .map_err(|e| anyhow!("Serialization error: {}", e))?,
+                    .map_err(|e| StorageError::storage("Serialization error", e))?,
             )
         };
 
@@ -95,7 +95,7 @@ impl StorageAdapter for SqliteStorageAdapter {
         .bind(metadata_json)
         .execute(&self.pool)
         .await
-        .map_err(|e| anyhow!("Operation failed: {}", e))?;
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
 
         Ok(())
     }
@@ -109,43 +109,55 @@ impl StorageAdapter for SqliteStorageAdapter {
             SELECT source_identifier, fetch_time, snapshot_json, metadata_json
             FROM exchange_rates
             WHERE source_identifier = ?
-            ORDER BY fetch_time DESC
-            LIMIT 1
             "#,
         )
         .bind(source_identifier)
         .fetch_optional(&self.pool)
         .await
-        .map_err(|e| anyhow!("Operation failed: {}", e))?;
-
-        match row {
-            Some(row) => {
-                let snapshot_json: String = row.get("snapshot_json");
-                let metadata_json: Option<String> = row.get("metadata_json");
-                let fetch_timestamp: i64 = row.get("fetch_time");
-
-                let snapshot = serde_json::from_str(&snapshot_json)
-                    .map_err(|e| anyhow!("Serialization error: {}", e))?;
-
-                let metadata = if let Some(json) = metadata_json {
-                    serde_json::from_str(&json)
-                        .map_err(|e| anyhow!("Serialization error: {}", e))?
-                } else {
-                    HashMap::new()
-                };
-
-                let fetch_time = DateTime::from_timestamp(fetch_timestamp, 0)
-                    .ok_or_else(|| anyhow!("Invalid timestamp"))?;
-
-                Ok(Some(ExchangeRateRecord {
-                    snapshot,
-                    fetch_timestamp: fetch_time,
-                    source_identifier: source_identifier.to_string(),
-                    metadata,
-                }))
-            }
-            None => Ok(None),
-        }
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        row.map(|row| record_from_row(&row)).transpose()
+    }
+
+    async fn get_newest_exchange_rates(&self, prefix: &str) -> Result<Option<ExchangeRateRecord>> {
+        let row = sqlx::query(
+            r#"
+            SELECT source_identifier, fetch_time, snapshot_json, metadata_json
+            FROM exchange_rates
+            WHERE substr(source_identifier, 1, length(?1)) = ?1
+            ORDER BY json_extract(snapshot_json, '$.observation_date') DESC,
+                rates_count DESC,
+                fetch_time DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(prefix)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        row.map(|row| record_from_row(&row)).transpose()
+    }
+
+    async fn touch_exchange_rates(
+        &self,
+        source_identifier: &str,
+        fetch_timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE exchange_rates
+            SET fetch_time = ?
+            WHERE source_identifier = ?
+            "#,
+        )
+        .bind(fetch_timestamp.timestamp())
+        .bind(source_identifier)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        Ok(())
     }
 
     async fn exchange_rates_exist(&self, source_identifier: &str) -> Result<bool> {
@@ -155,16 +167,109 @@ impl StorageAdapter for SqliteStorageAdapter {
         .bind(source_identifier)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| anyhow!("Operation failed: {}", e))?;
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
 
         Ok(count > 0)
     }
 
+    async fn get_last_sync(&self, sync_identifier: &str) -> Result<Option<DateTime<Utc>>> {
+        let synced_at: Option<i64> =
+            sqlx::query_scalar("SELECT synced_at FROM sync_state WHERE sync_identifier = ?")
+                .bind(sync_identifier)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        synced_at
+            .map(|synced_at| {
+                DateTime::from_timestamp(synced_at, 0).ok_or_else(|| {
+                    StorageError::storage("Invalid timestamp", synced_at.to_string())
+                })
+            })
+            .transpose()
+    }
+
+    async fn store_last_sync(&self, sync_identifier: &str, synced_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO sync_state (sync_identifier, synced_at)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(sync_identifier)
+        .bind(synced_at.timestamp())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        Ok(())
+    }
+
+    async fn store_codelist(&self, codelist: &Codelist) -> Result<()> {
+        let codelist_json = serde_json::to_string(codelist)
+            .map_err(|e| StorageError::storage("Serialization error", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO codelists (codelist_id, codelist_json)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(&codelist.id)
+        .bind(codelist_json)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        Ok(())
+    }
+
+    async fn get_codelist(&self, codelist_id: &str) -> Result<Option<Codelist>> {
+        let codelist_json: Option<String> =
+            sqlx::query_scalar("SELECT codelist_json FROM codelists WHERE codelist_id = ?")
+                .bind(codelist_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| StorageError::storage("Operation failed", e))?;
+
+        codelist_json
+            .map(|codelist_json| {
+                serde_json::from_str(&codelist_json)
+                    .map_err(|e| StorageError::storage("Serialization error", e))
+            })
+            .transpose()
+    }
+
     async fn health_check(&self) -> Result<()> {
         sqlx::query("SELECT 1")
             .fetch_one(&self.pool)
             .await
-            .map_err(|e| anyhow!("Connection error: {}", e))?;
+            .map_err(|e| StorageError::storage("Connection error", e))?;
         Ok(())
     }
 }
+
+fn record_from_row(row: &SqliteRow) -> Result<ExchangeRateRecord> {
+    let snapshot_json: String = row.get("snapshot_json");
+    let metadata_json: Option<String> = row.get("metadata_json");
+    let fetch_timestamp: i64 = row.get("fetch_time");
+
+    let snapshot = serde_json::from_str(&snapshot_json)
+        .map_err(|e| StorageError::storage("Serialization error", e))?;
+
+    let metadata = if let Some(json) = metadata_json {
+        serde_json::from_str(&json).map_err(|e| StorageError::storage("Serialization error", e))?
+    } else {
+        HashMap::new()
+    };
+
+    let fetch_time = DateTime::from_timestamp(fetch_timestamp, 0)
+        .ok_or_else(|| StorageError::storage("Invalid timestamp", fetch_timestamp.to_string()))?;
+
+    Ok(ExchangeRateRecord {
+        snapshot,
+        fetch_timestamp: fetch_time,
+        source_identifier: row.get("source_identifier"),
+        metadata,
+    })
+}
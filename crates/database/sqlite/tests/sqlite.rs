@@ -0,0 +1,188 @@
+use std::path::PathBuf;
+
+use bce_exchange_client::{ExchangeRate, ExchangeRatesSnapshot, ObservationStatus};
+use bce_exchange_database::{ExchangeRateRecord, StorageAdapter};
+use bce_exchange_database_sqlite::SqliteStorageAdapter;
+use bce_exchange_test_support::date;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// Database file of a test, removed along with its WAL files when dropped.
+struct TempDatabase(PathBuf);
+
+impl TempDatabase {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("bce-exchange-{}-{}.db", name, std::process::id()));
+        let database = Self(path);
+        database.remove();
+        database
+    }
+
+    async fn open(&self) -> SqliteStorageAdapter {
+        SqliteStorageAdapter::new(&self.0.to_string_lossy())
+            .await
+            .unwrap()
+    }
+
+    fn remove(&self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let mut path = self.0.clone().into_os_string();
+            path.push(suffix);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+impl Drop for TempDatabase {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
+fn fetched_at(rfc3339: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()
+}
+
+fn record(
+    source_identifier: &str,
+    observation_date: NaiveDate,
+    currencies: &[&str],
+    fetch_timestamp: DateTime<Utc>,
+) -> ExchangeRateRecord {
+    let rates = currencies
+        .iter()
+        .map(|currency| ExchangeRate {
+            currency: currency.to_string(),
+            rate: Some(1.0),
+            observation_date,
+            status: ObservationStatus::Normal,
+            confidentiality: None,
+        })
+        .collect();
+
+    ExchangeRateRecord::new(
+        ExchangeRatesSnapshot::from_rates(rates, fetch_timestamp.to_rfc3339()).unwrap(),
+        fetch_timestamp,
+        source_identifier.to_string(),
+    )
+}
+
+#[tokio::test]
+async fn newest_record_has_the_most_recent_fixing() {
+    let database = TempDatabase::new("newest");
+    let storage = database.open().await;
+
+    for record in [
+        record(
+            "D.USD.EUR.SP00.A/2025-03-27",
+            date(2025, 3, 27),
+            &["USD"],
+            fetched_at("2025-03-29T10:00:00Z"),
+        ),
+        record(
+            "D.USD.EUR.SP00.A/2025-03-28",
+            date(2025, 3, 28),
+            &["USD"],
+            fetched_at("2025-03-29T09:00:00Z"),
+        ),
+        record(
+            "D..EUR.SP00.A/2025-03-28",
+            date(2025, 3, 28),
+            &["JPY", "USD"],
+            fetched_at("2025-03-28T17:00:00Z"),
+        ),
+        record(
+            "M..EUR.SP00.A/2025-04",
+            date(2025, 4, 1),
+            &["JPY", "USD"],
+            fetched_at("2025-05-02T09:00:00Z"),
+        ),
+    ] {
+        storage.store_exchange_rates(record).await.unwrap();
+    }
+
+    let newest = storage
+        .get_newest_exchange_rates("D.")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(newest.source_identifier, "D..EUR.SP00.A/2025-03-28");
+    assert_eq!(newest.fetch_timestamp, fetched_at("2025-03-28T17:00:00Z"));
+
+    assert!(
+        storage
+            .get_newest_exchange_rates("Q.")
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn storing_a_source_again_replaces_its_record() {
+    let database = TempDatabase::new("replace");
+    let storage = database.open().await;
+
+    for (observation_date, fetch_timestamp) in [
+        (date(2025, 3, 27), fetched_at("2025-03-27T17:00:00Z")),
+        (date(2025, 3, 28), fetched_at("2025-03-28T17:00:00Z")),
+    ] {
+        storage
+            .store_exchange_rates(record(
+                "latest",
+                observation_date,
+                &["USD"],
+                fetch_timestamp,
+            ))
+            .await
+            .unwrap();
+    }
+
+    let latest = storage
+        .get_latest_exchange_rates("latest")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.snapshot.observation_date, date(2025, 3, 28));
+
+    let pool = sqlx::SqlitePool::connect(&database.0.to_string_lossy())
+        .await
+        .unwrap();
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM exchange_rates")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+#[tokio::test]
+async fn reads_records_stored_before_rates_were_dated() {
+    let database = TempDatabase::new("undated");
+    let storage = database.open().await;
+
+    let pool = sqlx::SqlitePool::connect(&database.0.to_string_lossy())
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO exchange_rates (source_identifier, fetch_time, snapshot_json) VALUES (?, ?, ?)",
+    )
+    .bind("2025-03-28")
+    .bind(fetched_at("2025-03-28T17:10:00Z").timestamp())
+    .bind(
+        r#"{"rates":[{"currency":"USD","rate":1.0807}],"timestamp":"2025-03-28T17:10:00.123+00:00"}"#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let record = storage
+        .get_latest_exchange_rates("2025-03-28")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.snapshot.observation_date, date(2025, 3, 28));
+    let rate = &record.snapshot.rates[0];
+    assert_eq!(rate.rate, Some(1.0807));
+    assert_eq!(rate.observation_date, date(2025, 3, 28));
+    assert_eq!(rate.status, ObservationStatus::Normal);
+}
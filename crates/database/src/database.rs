@@ -1,10 +1,46 @@
 use std::{collections::HashMap, sync::Arc};
 
-use anyhow::Result;
 use async_trait::async_trait;
-use bce_exchange_client::ExchangeRatesSnapshot;
+use bce_exchange_client::{Codelist, ExchangeRatesSnapshot};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+pub type Result<T, E = StorageError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+    #[error("{context}: {source}")]
+    Storage {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+    #[error("Migration {version} failed: {source}")]
+    Migration {
+        version: i32,
+        #[source]
+        source: BoxError,
+    },
+}
+
+impl StorageError {
+    pub fn storage(context: impl Into<String>, source: impl Into<BoxError>) -> Self {
+        Self::Storage {
+            context: context.into(),
+            source: source.into(),
+        }
+    }
+
+    pub fn migration(version: i32, source: impl Into<BoxError>) -> Self {
+        Self::Migration {
+            version,
+            source: source.into(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ExchangeRateRecord {
@@ -36,7 +72,24 @@ pub trait StorageAdapter: Send + Sync {
         &self,
         source_identifier: &str,
     ) -> Result<Option<ExchangeRateRecord>>;
+    /// The record holding the most recent fixing among those whose source
+    /// identifier starts with `prefix`. Ties go to the record with the most
+    /// rates, then to the most recently fetched one.
+    async fn get_newest_exchange_rates(&self, prefix: &str) -> Result<Option<ExchangeRateRecord>>;
+    /// Moves the fetch time of the latest record of `source_identifier` to
+    /// `fetch_timestamp`, for a snapshot the upstream confirmed unchanged.
+    async fn touch_exchange_rates(
+        &self,
+        source_identifier: &str,
+        fetch_timestamp: DateTime<Utc>,
+    ) -> Result<()>;
     async fn exchange_rates_exist(&self, source_identifier: &str) -> Result<bool>;
+    /// When the sync identified by `sync_identifier` last completed.
+    async fn get_last_sync(&self, sync_identifier: &str) -> Result<Option<DateTime<Utc>>>;
+    async fn store_last_sync(&self, sync_identifier: &str, synced_at: DateTime<Utc>) -> Result<()>;
+    /// The codelist stored under its id, replacing any previous version.
+    async fn store_codelist(&self, codelist: &Codelist) -> Result<()>;
+    async fn get_codelist(&self, codelist_id: &str) -> Result<Option<Codelist>>;
     async fn health_check(&self) -> Result<()>;
 }
 
@@ -63,7 +116,46 @@ impl BceDatabase {
             .await
     }
 
+    pub async fn get_newest_exchange_rates(
+        &self,
+        prefix: &str,
+    ) -> Result<Option<ExchangeRateRecord>> {
+        self.storage.get_newest_exchange_rates(prefix).await
+    }
+
     pub async fn store_exchange_rates(&self, record: ExchangeRateRecord) -> Result<()> {
         self.storage.store_exchange_rates(record).await
     }
+
+    pub async fn touch_exchange_rates(
+        &self,
+        source_identifier: &str,
+        fetch_timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        self.storage
+            .touch_exchange_rates(source_identifier, fetch_timestamp)
+            .await
+    }
+
+    pub async fn get_last_sync(&self, sync_identifier: &str) -> Result<Option<DateTime<Utc>>> {
+        self.storage.get_last_sync(sync_identifier).await
+    }
+
+    pub async fn store_last_sync(
+        &self,
+        sync_identifier: &str,
+        synced_at: DateTime<Utc>,
+    ) -> Result<()> {
+        self.storage
+            .store_last_sync(sync_identifier, synced_at)
+            .await
+    }
+
+    pub async fn store_codelist(&self, codelist: &Codelist) -> Result<()> {
+        self.storage.store_codelist(codelist).await
+    }
+
+    pub async fn get_codelist(&self, codelist_id: &str) -> Result<Option<Codelist>> {
+        self.storage.get_codelist(codelist_id).await
+    }
 }
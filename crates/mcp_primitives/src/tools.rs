@@ -1,6 +1,11 @@
-use anyhow::{Ok, Result, anyhow};
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
-use bce_exchange_provider::BceExchangeProvider;
+use bce_exchange_provider::{
+    BceExchangeProvider, Code, ExrSeries, Frequency, ProviderError, SeriesMetadata, SeriesVariation,
+};
+use chrono::NaiveDate;
 use context_server::{Tool, ToolContent, ToolExecutor};
 use schemars::{JsonSchema, schema_for};
 use serde::{Deserialize, Serialize};
@@ -14,12 +19,56 @@ struct CurrencyValue {
     amount: f64,
 }
 
+#[derive(Clone, Copy, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RateFrequency {
+    Daily,
+    Monthly,
+    Quarterly,
+    Annual,
+}
+
+#[derive(Clone, Copy, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RateVariation {
+    Average,
+    EndOfPeriod,
+}
+
 #[derive(Deserialize, JsonSchema, Serialize)]
 struct RateConversionItem {
     #[schemars(description = "Currency value to convert from")]
     from_value: CurrencyValue,
     #[schemars(description = "The target currency to convert to (e.g., EUR, USD, JPY)")]
     target_currency: String,
+    #[schemars(
+        description = "Any date (YYYY-MM-DD) within the period to use, e.g. 2025-03-01 for March 2025. Omit for the latest daily rate. A day without a daily fixing, such as a weekend, falls back to a nearby business day, reported as observation_date"
+    )]
+    period: Option<String>,
+    #[schemars(description = "Frequency of the rate series, daily by default")]
+    frequency: Option<RateFrequency>,
+    #[schemars(
+        description = "Average over the period (default) or the rate at the end of the period"
+    )]
+    variation: Option<RateVariation>,
+}
+
+impl RateConversionItem {
+    fn series(&self) -> ExrSeries {
+        let frequency = match self.frequency {
+            None | Some(RateFrequency::Daily) => Frequency::Daily,
+            Some(RateFrequency::Monthly) => Frequency::Monthly,
+            Some(RateFrequency::Quarterly) => Frequency::Quarterly,
+            Some(RateFrequency::Annual) => Frequency::Annual,
+        };
+
+        let variation = match self.variation {
+            None | Some(RateVariation::Average) => SeriesVariation::Average,
+            Some(RateVariation::EndOfPeriod) => SeriesVariation::EndOfPeriod,
+        };
+
+        ExrSeries::new(frequency, variation)
+    }
 }
 
 #[derive(Deserialize, JsonSchema, Serialize)]
@@ -28,16 +77,66 @@ struct RateConversionParams {
     conversions: Vec<RateConversionItem>,
 }
 
+/// Renders e.g. "Japanese yen (JPY), 2 decimals" from the ECB series metadata.
+fn describe_currency(currency: &str, series: Option<&SeriesMetadata>) -> String {
+    let Some(series) = series else {
+        return currency.to_string();
+    };
+
+    let mut description = match &series.currency_name {
+        Some(name) => format!("{} ({})", name, currency),
+        None => currency.to_string(),
+    };
+
+    if let Some(decimals) = series.decimals {
+        description.push_str(&format!(", {} decimals", decimals));
+    }
+
+    description
+}
+
 pub struct RateConversion {
-    ecb_exchange_provider: BceExchangeProvider,
+    ecb_exchange_provider: Arc<BceExchangeProvider>,
+    currencies: Vec<Code>,
 }
 
 impl RateConversion {
-    pub fn new(ecb_exchange_provider: BceExchangeProvider) -> Self {
+    /// Takes the provider either by value or shared, e.g. with a background
+    /// refresh running on it.
+    pub fn new(ecb_exchange_provider: impl Into<Arc<BceExchangeProvider>>) -> Self {
         Self {
-            ecb_exchange_provider,
+            ecb_exchange_provider: ecb_exchange_provider.into(),
+            currencies: Vec::new(),
         }
     }
+
+    /// Lists `currencies` in the tool description, typically the ones
+    /// returned by [`BceExchangeProvider::quoted_currencies`].
+    pub fn with_currencies(mut self, currencies: Vec<Code>) -> Self {
+        self.currencies = currencies;
+        self
+    }
+
+    fn description(&self) -> String {
+        let mut description = "Convert between different currencies using ECB exchange rates, either the latest daily reference rate or the daily, monthly, quarterly or annual average or end-of-period rate for a given period.".to_string();
+
+        if self.currencies.is_empty() {
+            description.push_str(" Supports EUR and the currencies quoted in the ECB euro foreign exchange reference rates, by ISO 4217 code");
+        } else {
+            let currencies: Vec<String> = self
+                .currencies
+                .iter()
+                .map(|code| match &code.name {
+                    Some(name) => format!("{} ({})", code.id, name),
+                    None => code.id.clone(),
+                })
+                .collect();
+
+            description.push_str(&format!(" Supports {}", currencies.join(", ")));
+        }
+
+        description
+    }
 }
 
 #[async_trait]
@@ -52,24 +151,96 @@ impl ToolExecutor for RateConversion {
         let mut results = Vec::new();
 
         for conversion in params.conversions {
-            let rate = self
-                .ecb_exchange_provider
-                .rate_conversion(&conversion.from_value.currency, &conversion.target_currency)
-                .await?;
+            let result = match &conversion.period {
+                Some(period) => {
+                    let date = NaiveDate::parse_from_str(period, "%Y-%m-%d")
+                        .map_err(|_| anyhow!("Invalid period {}, expected YYYY-MM-DD", period))?;
 
-            let converted_amount = conversion.from_value.amount * rate;
+                    if conversion.series() == ExrSeries::daily() {
+                        self.ecb_exchange_provider
+                            .rate_conversion_on(
+                                &conversion.from_value.currency,
+                                &conversion.target_currency,
+                                date,
+                            )
+                            .await
+                    } else {
+                        self.ecb_exchange_provider
+                            .rate_conversion_for_period(
+                                &conversion.from_value.currency,
+                                &conversion.target_currency,
+                                conversion.series(),
+                                date,
+                            )
+                            .await
+                    }
+                }
+                None if conversion.frequency.is_some() || conversion.variation.is_some() => {
+                    return Err(anyhow!("A period is required when choosing a rate series"));
+                }
+                None => {
+                    self.ecb_exchange_provider
+                        .rate_conversion(
+                            &conversion.from_value.currency,
+                            &conversion.target_currency,
+                        )
+                        .await
+                }
+            };
+
+            let conversion_rate = match result {
+                Ok(conversion_rate) => conversion_rate,
+                // The model can recover from these by picking another currency
+                // or date, so they are reported next to the other conversions
+                // rather than failing the whole call.
+                Err(
+                    error @ (ProviderError::UnknownCurrency(_)
+                    | ProviderError::NoDataForDate(_)
+                    | ProviderError::MissingRate { .. }),
+                ) => {
+                    results.push(json!({
+                        "error": error.to_string(),
+                        "from": {
+                            "currency": conversion.from_value.currency,
+                            "amount": conversion.from_value.amount
+                        },
+                        "to": {
+                            "currency": conversion.target_currency
+                        }
+                    }));
+                    continue;
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            let converted_amount = conversion_rate.rate.convert(conversion.from_value.amount);
 
-            results.push(json!({
-                "rate": rate,
+            let mut result = json!({
+                "rate": conversion_rate.rate.value,
+                "observation_date": conversion_rate.rate.date.to_string(),
+                "observation_status": conversion_rate.status.to_string(),
+                "series": conversion.series().key(),
                 "from": {
                     "currency": conversion.from_value.currency,
+                    "description": describe_currency(&conversion.from_value.currency, conversion_rate.from_series.as_ref()),
                     "amount": conversion.from_value.amount
                 },
                 "to": {
                     "currency": conversion.target_currency,
+                    "description": describe_currency(&conversion.target_currency, conversion_rate.to_series.as_ref()),
                     "amount": converted_amount
                 }
-            }));
+            });
+
+            // Lets the model tell the user the rates may be outdated.
+            if let Some(stale) = &conversion_rate.stale {
+                result["stale"] = json!({
+                    "fetched_at": stale.fetched_at.to_rfc3339(),
+                    "reason": stale.reason
+                });
+            }
+
+            results.push(result);
         }
 
         Ok(vec![ToolContent::Text {
@@ -80,7 +251,7 @@ impl ToolExecutor for RateConversion {
     fn to_tool(&self) -> Tool {
         Tool {
             name: "rate_conversion".into(),
-            description: Some("Convert between different currencies using ECB exchange rates. Supports major currencies including USD, JPY, BGN, CZK, DKK, GBP, HUF, PLN, RON, SEK, CHF, ISK, NOK, TRY, AUD, BRL, CAD, CNY, HKD, IDR, ILS, INR, KRW, MXN, MYR, NZD, PHP, SGD, THB, ZAR".into()),
+            description: Some(self.description()),
             input_schema: schema_for!(RateConversionParams).to_value(),
         }
     }
@@ -0,0 +1,144 @@
+use bce_exchange_mcp_primitives::tools::RateConversion;
+use bce_exchange_test_support::{DATA_API, MockHttpClient, fixtures, provider};
+use context_server::{ToolContent, ToolExecutor};
+use serde_json::{Value, json};
+
+async fn execute(http_client: &MockHttpClient, arguments: Value) -> anyhow::Result<Value> {
+    let tool = RateConversion::new(provider(http_client));
+
+    let content = tool.execute(Some(arguments)).await?;
+
+    let [ToolContent::Text { text }] = content.as_slice() else {
+        panic!("expected a single text content");
+    };
+
+    Ok(serde_json::from_str(text)?)
+}
+
+#[tokio::test]
+async fn converts_with_latest_rates() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let results = execute(
+        &http_client,
+        json!({
+            "conversions": [{
+                "from_value": { "currency": "USD", "amount": 100.0 },
+                "target_currency": "JPY"
+            }]
+        }),
+    )
+    .await
+    .unwrap();
+
+    let result = &results[0];
+    assert_eq!(result["observation_date"], "2025-03-28");
+    assert_eq!(result["observation_status"], "normal");
+    assert_eq!(result["series"], "D..EUR.SP00.A");
+    assert_eq!(result["from"]["description"], "US dollar (USD), 4 decimals");
+    assert_eq!(
+        result["to"]["description"],
+        "Japanese yen (JPY), 2 decimals"
+    );
+}
+
+#[tokio::test]
+async fn unknown_currency_is_reported_per_conversion() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let results = execute(
+        &http_client,
+        json!({
+            "conversions": [
+                {
+                    "from_value": { "currency": "XYZ", "amount": 1.0 },
+                    "target_currency": "EUR"
+                },
+                {
+                    "from_value": { "currency": "GBP", "amount": 1.0 },
+                    "target_currency": "EUR"
+                }
+            ]
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(results[0]["error"], "Currency XYZ not found in snapshot");
+    assert_eq!(results[1]["observation_date"], "2025-03-28");
+}
+
+#[tokio::test]
+async fn missing_observation_is_reported_per_conversion() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::missing_observation());
+
+    let results = execute(
+        &http_client,
+        json!({
+            "conversions": [
+                {
+                    "from_value": { "currency": "JPY", "amount": 1.0 },
+                    "target_currency": "EUR",
+                    "period": "2025-03-28"
+                },
+                {
+                    "from_value": { "currency": "USD", "amount": 1.0 },
+                    "target_currency": "EUR",
+                    "period": "2025-03-28"
+                }
+            ]
+        }),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        results[0]["error"],
+        "No JPY rate for 2025-03-28, the observation is missing"
+    );
+    assert_eq!(results[1]["observation_date"], "2025-03-28");
+}
+
+#[tokio::test]
+async fn series_options_require_a_period() {
+    let http_client = MockHttpClient::new();
+
+    let error = execute(
+        &http_client,
+        json!({
+            "conversions": [{
+                "from_value": { "currency": "USD", "amount": 1.0 },
+                "target_currency": "EUR",
+                "frequency": "monthly"
+            }]
+        }),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error.to_string().contains("period"), "{error}");
+    assert!(http_client.requests().is_empty());
+}
+
+#[tokio::test]
+async fn description_lists_quoted_currencies() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    http_client.respond("/codelist/ECB/CL_CURRENCY", fixtures::codelist_currency());
+
+    let provider = provider(&http_client);
+    let currencies = provider.quoted_currencies().await.unwrap();
+    let tool = RateConversion::new(provider).with_currencies(currencies);
+
+    let description = tool.to_tool().description.unwrap();
+
+    assert!(
+        description.ends_with(
+            "Supports CHF (Swiss franc), EUR (Euro), GBP (UK pound sterling), JPY (Japanese yen), USD (US dollar)"
+        ),
+        "{description}"
+    );
+}
@@ -0,0 +1,113 @@
+//! Policies deciding when the cached latest reference rates are served as is
+//! and when the ECB is asked again.
+
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+
+use crate::{
+    calendar::{self, PUBLICATION_TIME},
+    clock,
+};
+
+/// Freshness of the cached latest reference rates.
+pub trait CachePolicy: Send + Sync {
+    /// Whether a snapshot whose latest fixing is `latest_fixing`, fetched at
+    /// `fetched_at`, can be served at `now` without asking the ECB.
+    fn is_fresh(
+        &self,
+        latest_fixing: NaiveDate,
+        fetched_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool;
+
+    /// Whether a cached snapshot ever stops being fresh. A background refresh
+    /// is only worth running when it does.
+    fn expires(&self) -> bool {
+        true
+    }
+}
+
+/// Follows the ECB publication schedule: a snapshot stays fresh until the
+/// next fixing is due, so weekends and TARGET holidays cause no request.
+#[derive(Debug, Clone, Copy)]
+pub struct PublicationSchedule {
+    /// Time, in Paris, after which the fixing of a publication day is
+    /// expected.
+    pub publication_time: NaiveTime,
+    /// How long a snapshot missing the expected fixing is served before
+    /// asking again, as the ECB is sometimes late.
+    pub late_recheck: TimeDelta,
+}
+
+/// Serves a snapshot for a fixed time after it was fetched.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedTtl(pub Duration);
+
+/// Serves whatever is cached, only fetching when nothing is, e.g. for offline
+/// use with a synced history.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeverRefresh;
+
+/// Asks the ECB on every conversion, conditionally when the cached snapshot
+/// carries validators.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AlwaysRefresh;
+
+impl Default for PublicationSchedule {
+    fn default() -> Self {
+        Self {
+            publication_time: PUBLICATION_TIME,
+            late_recheck: TimeDelta::minutes(15),
+        }
+    }
+}
+
+impl CachePolicy for PublicationSchedule {
+    fn is_fresh(
+        &self,
+        latest_fixing: NaiveDate,
+        fetched_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        latest_fixing >= calendar::last_fixing_date(now, self.publication_time)
+            || now - fetched_at < self.late_recheck
+    }
+}
+
+impl CachePolicy for FixedTtl {
+    fn is_fresh(&self, _: NaiveDate, fetched_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        clock::age(fetched_at, now) < self.0
+    }
+}
+
+impl CachePolicy for NeverRefresh {
+    fn is_fresh(&self, _: NaiveDate, _: DateTime<Utc>, _: DateTime<Utc>) -> bool {
+        true
+    }
+
+    fn expires(&self) -> bool {
+        false
+    }
+}
+
+impl CachePolicy for AlwaysRefresh {
+    fn is_fresh(&self, _: NaiveDate, _: DateTime<Utc>, _: DateTime<Utc>) -> bool {
+        false
+    }
+}
+
+impl<P: CachePolicy + ?Sized> CachePolicy for Box<P> {
+    fn is_fresh(
+        &self,
+        latest_fixing: NaiveDate,
+        fetched_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        (**self).is_fresh(latest_fixing, fetched_at, now)
+    }
+
+    fn expires(&self) -> bool {
+        (**self).expires()
+    }
+}
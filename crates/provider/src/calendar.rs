@@ -0,0 +1,107 @@
+//! TARGET closing days and the ECB publication schedule of the euro foreign
+//! exchange reference rates.
+//!
+//! The ECB publishes a fixing on every TARGET business day, around 16:00
+//! Central European Time. TARGET is closed on weekends, New Year's Day, Good
+//! Friday, Easter Monday, 1 May, Christmas Day and 26 December.
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Europe::Paris;
+
+/// Time, in Paris, at which the ECB usually releases the fixing of a
+/// publication day.
+pub const PUBLICATION_TIME: NaiveTime = NaiveTime::from_hms_opt(16, 0, 0).unwrap();
+
+/// Easter Sunday of `year` in the Gregorian calendar, following the
+/// anonymous Gregorian algorithm (Meeus/Jones/Butcher). `None` only for years
+/// out of `chrono`'s range.
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Whether TARGET is closed on `date` for a holiday, weekends aside.
+pub fn is_target_holiday(date: NaiveDate) -> bool {
+    if matches!(
+        (date.month(), date.day()),
+        (1, 1) | (5, 1) | (12, 25) | (12, 26)
+    ) {
+        return true;
+    }
+
+    easter_sunday(date.year()).is_some_and(|easter| {
+        easter.checked_sub_days(Days::new(2)) == Some(date)
+            || easter.checked_add_days(Days::new(1)) == Some(date)
+    })
+}
+
+/// Whether the ECB publishes a fixing on `date`.
+pub fn is_publication_day(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !is_target_holiday(date)
+}
+
+/// The last publication day on or before `date`.
+pub fn previous_publication_day(date: NaiveDate) -> NaiveDate {
+    date.iter_days()
+        .rev()
+        .find(|day| is_publication_day(*day))
+        .unwrap_or(date)
+}
+
+/// The first publication day on or after `date`.
+pub fn next_publication_day(date: NaiveDate) -> NaiveDate {
+    date.iter_days()
+        .find(|day| is_publication_day(*day))
+        .unwrap_or(date)
+}
+
+/// Date of the most recent fixing published as of `at`, given it is released
+/// at `publication_time` in Paris: today's once that time has passed on a
+/// publication day, the previous publication day's otherwise.
+pub fn last_fixing_date<Tz: TimeZone>(at: DateTime<Tz>, publication_time: NaiveTime) -> NaiveDate {
+    let paris = at.with_timezone(&Paris);
+    let today = paris.date_naive();
+
+    if is_publication_day(today) && paris.time() >= publication_time {
+        return today;
+    }
+
+    previous_publication_day(today.pred_opt().unwrap_or(today))
+}
+
+/// The first publication instant, `publication_time` in Paris on a
+/// publication day, strictly after `after`.
+pub fn next_publication<Tz: TimeZone>(
+    after: DateTime<Tz>,
+    publication_time: NaiveTime,
+) -> DateTime<Utc> {
+    let after = after.with_timezone(&Utc);
+
+    after
+        .with_timezone(&Paris)
+        .date_naive()
+        .iter_days()
+        .filter(|day| is_publication_day(*day))
+        .filter_map(|day| {
+            Paris
+                .from_local_datetime(&day.and_time(publication_time))
+                .earliest()
+        })
+        .map(|instant| instant.with_timezone(&Utc))
+        .find(|instant| *instant > after)
+        .unwrap_or(after)
+}
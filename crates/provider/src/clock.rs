@@ -0,0 +1,32 @@
+//! Source of the current time, injectable so that the cache and the
+//! background refresh can be driven by a simulated clock.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Arc<C> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// Time elapsed at `now` since `fetched_at`. A negative age, from a clock set
+/// back, counts as just fetched.
+pub(crate) fn age(fetched_at: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    (now - fetched_at).to_std().unwrap_or_default()
+}
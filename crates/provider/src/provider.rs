@@ -1,86 +1,678 @@
-use std::sync::Arc;
+mod cache_policy;
+pub mod calendar;
+mod clock;
+mod rate;
+mod refresh;
+mod sync;
 
-use anyhow::{Result, anyhow};
-use bce_exchange_client::{BceClient, ExchangeRatesSnapshot};
-use bce_exchange_database::{BceDatabase, ExchangeRateRecord, StorageAdapter};
-use chrono::{Timelike, Utc};
-use chrono_tz::Europe::Paris;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bce_exchange_client::{
+    BceClient, CacheValidators, ClientError, Codelist, Conditional, EuroFxRefClient,
+    ExchangeRatesSnapshot, ExrKey,
+};
+pub use bce_exchange_client::{
+    BceClientConfig, Code, ExrSeries, Frequency, ObservationStatus, SeriesMetadata, SeriesVariation,
+};
+use bce_exchange_database::{BceDatabase, ExchangeRateRecord, StorageAdapter, StorageError};
+use chrono::{DateTime, Days, NaiveDate, Utc};
 use http_client::HttpClient;
+use thiserror::Error;
+
+pub use crate::{
+    cache_policy::{AlwaysRefresh, CachePolicy, FixedTtl, NeverRefresh, PublicationSchedule},
+    clock::{Clock, SystemClock},
+    rate::Rate,
+    refresh::RefreshSchedule,
+    sync::SyncReport,
+};
+use crate::{refresh::RefreshTask, sync::history_cache_key};
+
+/// ECB codelist naming the currencies of the `CURRENCY` dimension.
+const CURRENCY_CODELIST: &str = "CL_CURRENCY";
+
+/// Days searched around a requested date for a fixing. TARGET is never
+/// closed for more than four days in a row (Good Friday to Easter Monday).
+const FALLBACK_WINDOW: Days = Days::new(7);
+
+/// Default for how long after it was last fetched a snapshot may be served
+/// while the ECB is unreachable, long enough to cover an Easter weekend.
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Record key of the latest daily reference rates. Each fixing fetched as the
+/// latest is also merged into the daily history under its own date, where it
+/// stays once a later fixing replaces it here.
+const LATEST_CACHE_KEY: &str = "latest";
+
+/// Record metadata keys holding the validators of the ECB response a snapshot
+/// was decoded from.
+const ETAG_METADATA_KEY: &str = "etag";
+const LAST_MODIFIED_METADATA_KEY: &str = "last_modified";
+
+pub type Result<T, E = ProviderError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error("ECB data API and eurofxref fallback both failed: {fallback}")]
+    Unavailable {
+        #[source]
+        primary: ClientError,
+        fallback: ClientError,
+    },
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+    #[error("Currency {0} not found in snapshot")]
+    UnknownCurrency(String),
+    #[error("No exchange rates published for {0}")]
+    NoDataForDate(NaiveDate),
+    #[error("No {currency} rate for {date}, the observation is {status}")]
+    MissingRate {
+        currency: String,
+        date: NaiveDate,
+        status: ObservationStatus,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ConversionRate {
+    /// Quoted as source/target currency, dated after the oldest ECB fixing it
+    /// was derived from.
+    pub rate: Rate,
+    /// `Normal` unless one of the rates involved is not a regular fixing.
+    pub status: ObservationStatus,
+    /// Series metadata of the source currency, `None` for EUR.
+    pub from_series: Option<SeriesMetadata>,
+    /// Series metadata of the target currency, `None` for EUR.
+    pub to_series: Option<SeriesMetadata>,
+    /// Set when the ECB could not be reached and an older stored snapshot
+    /// was used instead.
+    pub stale: Option<Staleness>,
+}
+
+/// Why and how stale the rates of a [`ConversionRate`] are.
+#[derive(Debug, Clone)]
+pub struct Staleness {
+    /// When the snapshot used was last fetched from, or confirmed by, the
+    /// ECB.
+    pub fetched_at: DateTime<Utc>,
+    /// The error that prevented fetching current rates.
+    pub reason: String,
+}
+
+/// Fixing used by [`BceExchangeProvider::rate_conversion_on`] when the ECB
+/// published none on the requested date, such as on a weekend or a TARGET
+/// holiday.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FallbackPolicy {
+    /// The last fixing before the date, as is customary for expenses.
+    #[default]
+    PreviousBusinessDay,
+    /// The first fixing after the date.
+    NextBusinessDay,
+    /// No fallback: fail with [`ProviderError::NoDataForDate`].
+    Strict,
+}
 
 pub struct BceExchangeProvider {
     client: BceClient,
+    eurofxref_client: EuroFxRefClient,
     database: BceDatabase,
+    cache_policy: Box<dyn CachePolicy>,
+    fallback_policy: FallbackPolicy,
+    max_staleness: Option<Duration>,
+    clock: Arc<dyn Clock>,
+    refresh_task: Mutex<Option<RefreshTask>>,
 }
 
 impl BceExchangeProvider {
     pub fn new<S>(http_client: Arc<dyn HttpClient>, storage_adapter: S) -> Self
+    where
+        S: StorageAdapter + 'static,
+    {
+        Self::with_client_config(http_client, storage_adapter, BceClientConfig::default())
+    }
+
+    /// Like [`new`](Self::new), reaching the ECB as described by
+    /// `client_config`.
+    pub fn with_client_config<S>(
+        http_client: Arc<dyn HttpClient>,
+        storage_adapter: S,
+        client_config: BceClientConfig,
+    ) -> Self
     where
         S: StorageAdapter + 'static,
     {
         Self {
-            client: BceClient::new(http_client),
+            client: BceClient::new(http_client.clone()).with_config(client_config.clone()),
+            eurofxref_client: EuroFxRefClient::new(http_client).with_config(client_config),
             database: BceDatabase::new(storage_adapter),
+            cache_policy: Box::new(PublicationSchedule::default()),
+            fallback_policy: FallbackPolicy::default(),
+            max_staleness: Some(DEFAULT_MAX_STALENESS),
+            clock: Arc::new(SystemClock),
+            refresh_task: Mutex::new(None),
         }
     }
 
-    pub async fn rate_conversion(&self, from_currency: &str, to_currency: &str) -> Result<f64> {
-        let snapshot = self.fetch_exchange_rates().await?;
+    /// Reads the current time from `clock` rather than the system.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Decides when the latest rates are fetched again rather than served
+    /// from the cache, [`PublicationSchedule`] by default.
+    pub fn with_cache_policy(mut self, cache_policy: impl CachePolicy + 'static) -> Self {
+        self.cache_policy = Box::new(cache_policy);
+        self
+    }
+
+    pub fn with_fallback_policy(mut self, fallback_policy: FallbackPolicy) -> Self {
+        self.fallback_policy = fallback_policy;
+        self
+    }
+
+    /// How long after it was last fetched the most recent stored fixing may be
+    /// served when neither the data API nor the eurofxref feed answers. Such
+    /// conversions are marked [`stale`](ConversionRate::stale). `None` fails
+    /// instead; the default is [`DEFAULT_MAX_STALENESS`].
+    pub fn with_max_staleness(mut self, max_staleness: Option<Duration>) -> Self {
+        self.max_staleness = max_staleness;
+        self
+    }
+
+    pub async fn rate_conversion(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+    ) -> Result<ConversionRate> {
+        let from_currency = currency_code(from_currency)?;
+        let to_currency = currency_code(to_currency)?;
+
+        let (snapshot, stale) = self.fetch_exchange_rates().await?;
+
+        Ok(ConversionRate {
+            stale,
+            ..self.convert(&snapshot, &from_currency, &to_currency)?
+        })
+    }
+
+    /// Currencies quoted in the latest daily reference rates, EUR included,
+    /// sorted by code. Names come from the ECB `CL_CURRENCY` codelist, or from
+    /// the series metadata when the codelist cannot be fetched.
+    pub async fn quoted_currencies(&self) -> Result<Vec<Code>> {
+        let (snapshot, _) = self.fetch_exchange_rates().await?;
 
-        let rate = if to_currency == "EUR" {
-            self.get_rate_to_eur(&snapshot, from_currency)?
-        } else if from_currency == "EUR" {
-            let eur_to_target = self.get_rate_from_eur(&snapshot, to_currency)?;
-            1.0 / eur_to_target
-        } else {
-            let from_to_eur = self.get_rate_to_eur(&snapshot, from_currency)?;
-            let eur_to_target = self.get_rate_from_eur(&snapshot, to_currency)?;
-            from_to_eur / eur_to_target
+        let names: HashMap<String, String> = match self.currency_codelist().await {
+            Some(codelist) => codelist
+                .codes
+                .into_iter()
+                .filter_map(|code| Some((code.id, code.name?)))
+                .collect(),
+            None => snapshot
+                .series
+                .iter()
+                .filter_map(|series| Some((series.currency.clone(), series.currency_name.clone()?)))
+                .collect(),
         };
 
-        Ok(rate)
+        // Discontinued series, e.g. RUB or HRK, still report their last
+        // observation, long before the latest fixing.
+        let mut currencies: Vec<String> = snapshot
+            .rates
+            .iter()
+            .filter(|rate| rate.observation_date == snapshot.observation_date)
+            .map(|rate| rate.currency.clone())
+            .chain(["EUR".to_string()])
+            .collect();
+        currencies.sort();
+        currencies.dedup();
+
+        Ok(currencies
+            .into_iter()
+            .map(|currency| Code {
+                name: names.get(&currency).cloned(),
+                id: currency,
+            })
+            .collect())
     }
 
-    async fn fetch_exchange_rates(&self) -> Result<ExchangeRatesSnapshot> {
-        let now = Utc::now();
-        let today = now.date_naive();
-        let cache_key = today.to_string();
+    /// Converts using the daily reference rates of `date`. Without a fixing
+    /// on that date, the [`FallbackPolicy`] picks another one; the date of
+    /// the fixing actually used is the one of the returned rate.
+    pub async fn rate_conversion_on(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        date: NaiveDate,
+    ) -> Result<ConversionRate> {
+        let from_currency = currency_code(from_currency)?;
+        let to_currency = currency_code(to_currency)?;
 
-        if let Ok(Some(record)) = self.database.get_latest_exchange_rates(&cache_key).await {
-            let cet_now = now.with_timezone(&Paris);
-            if cet_now.hour() < 17 {
-                return Ok(record.snapshot);
-            }
+        let snapshot = self
+            .fetch_fixing_on(&[&from_currency, &to_currency], date)
+            .await?;
 
-            let cet_fetch_time = record.fetch_timestamp.with_timezone(&Paris);
-            if cet_fetch_time.date_naive() == today && cet_fetch_time.hour() >= 17 {
-                return Ok(record.snapshot);
-            }
+        self.convert(&snapshot, &from_currency, &to_currency)
+    }
+
+    /// Converts using the observation of `series` for the period containing
+    /// `date`, e.g. the monthly average for March 2025 or the end-of-period
+    /// rate of a quarter.
+    pub async fn rate_conversion_for_period(
+        &self,
+        from_currency: &str,
+        to_currency: &str,
+        series: ExrSeries,
+        date: NaiveDate,
+    ) -> Result<ConversionRate> {
+        let from_currency = currency_code(from_currency)?;
+        let to_currency = currency_code(to_currency)?;
+
+        let snapshot = self
+            .fetch_series_period(series, &[&from_currency, &to_currency], date)
+            .await?;
+
+        self.convert(&snapshot, &from_currency, &to_currency)
+    }
+
+    fn convert(
+        &self,
+        snapshot: &ExchangeRatesSnapshot,
+        from_currency: &str,
+        to_currency: &str,
+    ) -> Result<ConversionRate> {
+        let (eur_from, from_status) = self.reference_rate(snapshot, from_currency)?;
+        let (eur_to, to_status) = self.reference_rate(snapshot, to_currency)?;
+
+        Ok(ConversionRate {
+            rate: eur_from.cross(&eur_to),
+            status: if from_status.is_normal() {
+                to_status
+            } else {
+                from_status
+            },
+            from_series: snapshot.series_metadata(from_currency).cloned(),
+            to_series: snapshot.series_metadata(to_currency).cloned(),
+            stale: None,
+        })
+    }
+
+    /// The latest reference rates, along with their staleness when they had
+    /// to be served from storage because of an outage.
+    async fn fetch_exchange_rates(&self) -> Result<(ExchangeRatesSnapshot, Option<Staleness>)> {
+        self.fetch_latest_with(self.cache_policy.as_ref()).await
+    }
+
+    async fn fetch_latest_with(
+        &self,
+        cache_policy: &dyn CachePolicy,
+    ) -> Result<(ExchangeRatesSnapshot, Option<Staleness>)> {
+        let now = self.clock.now();
+
+        let cached = self
+            .database
+            .get_latest_exchange_rates(LATEST_CACHE_KEY)
+            .await
+            .ok()
+            .flatten();
+
+        if let Some(record) = &cached
+            && cache_policy.is_fresh(
+                record.snapshot.observation_date,
+                record.fetch_timestamp,
+                now,
+            )
+        {
+            return Ok((record.snapshot.clone(), None));
         }
 
-        let snapshot = self.client.fetch_all_exchange_rates().await?;
+        let validators = cached
+            .as_ref()
+            .map(|record| cache_validators(&record.metadata))
+            .unwrap_or_default();
+
+        let fetched = match self
+            .client
+            .fetch_all_exchange_rates_if_modified(&validators)
+            .await
+        {
+            Ok(fetched) => fetched,
+            Err(primary) => match self.eurofxref_client.fetch_daily().await {
+                Ok(snapshot) => Conditional::Modified {
+                    value: snapshot,
+                    validators: CacheValidators::default(),
+                },
+                Err(fallback) => {
+                    let error = ProviderError::Unavailable { primary, fallback };
+                    return self.stale_if_error(cached, now, error).await;
+                }
+            },
+        };
+
+        let record = match (fetched, cached) {
+            // The cached snapshot is still current: only record that it was
+            // checked.
+            (Conditional::NotModified, Some(record)) => {
+                self.database
+                    .touch_exchange_rates(LATEST_CACHE_KEY, now)
+                    .await?;
+
+                return Ok((record.snapshot, None));
+            }
+            // Nothing was cached to send validators for, so the upstream
+            // answered a plain request with a 304.
+            (Conditional::NotModified, None) => return Err(ClientError::NoData.into()),
+            (
+                Conditional::Modified {
+                    value: snapshot,
+                    validators,
+                },
+                _,
+            ) => {
+                let mut record =
+                    ExchangeRateRecord::new(snapshot, now, LATEST_CACHE_KEY.to_string());
+                record.metadata = validators_metadata(validators);
+                record
+            }
+        };
 
-        let record = ExchangeRateRecord::new(snapshot.clone(), now, cache_key);
+        let snapshot = record.snapshot.clone();
 
         self.database.store_exchange_rates(record).await?;
+        self.store_latest_fixing(&snapshot).await?;
 
-        Ok(snapshot)
+        Ok((snapshot, None))
     }
 
-    fn get_rate_to_eur(&self, snapshot: &ExchangeRatesSnapshot, currency: &str) -> Result<f64> {
-        snapshot
+    /// Merges the rates of `snapshot` observed on its fixing date into the
+    /// daily history. Rates of series that stopped being published are left
+    /// out, as they belong to earlier fixings.
+    async fn store_latest_fixing(&self, snapshot: &ExchangeRatesSnapshot) -> Result<()> {
+        let rates = snapshot
             .rates
             .iter()
-            .find(|rate| rate.currency == currency)
-            .map(|rate| rate.rate)
-            .ok_or_else(|| anyhow!("Currency {} not found in snapshot", currency))
+            .filter(|rate| rate.observation_date == snapshot.observation_date)
+            .cloned()
+            .collect();
+
+        let Some(fixing) = ExchangeRatesSnapshot::from_rates(rates, snapshot.timestamp.clone())
+        else {
+            return Ok(());
+        };
+
+        self.merge_into_history(
+            ExrSeries::daily(),
+            fixing.with_series_metadata(&snapshot.series),
+        )
+        .await
+    }
+
+    /// Serves the most recent stored fixing in place of the rates `error`
+    /// prevented fetching, as long as it is within the maximum staleness.
+    /// Besides `cached`, that may be a fixing stored by a conversion on a
+    /// date or by a history sync.
+    async fn stale_if_error(
+        &self,
+        cached: Option<ExchangeRateRecord>,
+        now: DateTime<Utc>,
+        error: ProviderError,
+    ) -> Result<(ExchangeRatesSnapshot, Option<Staleness>)> {
+        let Some(max_staleness) = self.max_staleness else {
+            return Err(error);
+        };
+
+        let daily_prefix = format!("{}.", ExrSeries::daily().frequency.code());
+        let stored = self
+            .database
+            .get_newest_exchange_rates(&daily_prefix)
+            .await
+            .ok()
+            .flatten();
+
+        let Some(record) = cached.into_iter().chain(stored).max_by_key(|record| {
+            (
+                record.snapshot.observation_date,
+                record.snapshot.rates.len(),
+                record.fetch_timestamp,
+            )
+        }) else {
+            return Err(error);
+        };
+
+        if clock::age(record.fetch_timestamp, now) > max_staleness {
+            return Err(error);
+        }
+
+        Ok((
+            record.snapshot,
+            Some(Staleness {
+                fetched_at: record.fetch_timestamp,
+                reason: error.to_string(),
+            }),
+        ))
+    }
+
+    /// Fetches the period containing `date` for the non-EUR `currencies`
+    /// only, rather than for all ~30 quoted currencies.
+    async fn fetch_series_period(
+        &self,
+        series: ExrSeries,
+        currencies: &[&str],
+        date: NaiveDate,
+    ) -> Result<ExchangeRatesSnapshot> {
+        let key = quoted_key(series, currencies);
+
+        if let Some(snapshot) = self.cached_period(&key, date).await {
+            return Ok(snapshot);
+        }
+
+        let snapshot = self
+            .client
+            .fetch_series_between(key.clone(), date, date)
+            .await?
+            .pop()
+            .ok_or(ProviderError::NoDataForDate(date))?;
+
+        self.store_period(&key, &snapshot).await?;
+
+        Ok(snapshot)
+    }
+
+    /// Fetches the daily fixing of `date` for the non-EUR `currencies`, or
+    /// the one the fallback policy picks when there is none.
+    async fn fetch_fixing_on(
+        &self,
+        currencies: &[&str],
+        date: NaiveDate,
+    ) -> Result<ExchangeRatesSnapshot> {
+        let series = ExrSeries::daily();
+
+        let (start, end, fixing_date) = match self.fallback_policy {
+            FallbackPolicy::Strict => {
+                return self.fetch_series_period(series, currencies, date).await;
+            }
+            FallbackPolicy::PreviousBusinessDay => (
+                date - FALLBACK_WINDOW,
+                date,
+                calendar::previous_publication_day(date),
+            ),
+            FallbackPolicy::NextBusinessDay => (
+                date,
+                date + FALLBACK_WINDOW,
+                calendar::next_publication_day(date),
+            ),
+        };
+
+        let key = quoted_key(series, currencies);
+
+        // A weekend or holiday is answered by the fixing the calendar expects
+        // it to fall back to, which is cached under its own date.
+        if let Some(snapshot) = self.cached_period(&key, fixing_date).await {
+            return Ok(snapshot);
+        }
+
+        let mut snapshots = self
+            .client
+            .fetch_series_between(key.clone(), start, end)
+            .await?;
+
+        // Every fixing of the window is kept, each under its own date, so
+        // later conversions on those dates are answered from the cache.
+        for snapshot in &snapshots {
+            self.store_period(&key, snapshot).await?;
+        }
+
+        let snapshot = match self.fallback_policy {
+            FallbackPolicy::NextBusinessDay => snapshots.into_iter().next(),
+            _ => snapshots.pop(),
+        };
+
+        snapshot.ok_or(ProviderError::NoDataForDate(date))
+    }
+
+    /// Snapshot of the period containing `date` cached for `key`, or taken
+    /// from a synced history.
+    async fn cached_period(&self, key: &ExrKey, date: NaiveDate) -> Option<ExchangeRatesSnapshot> {
+        let series = key.series();
+
+        // A synced history holds every currency, so it can answer any pair.
+        if let Ok(Some(record)) = self
+            .database
+            .get_latest_exchange_rates(&history_cache_key(series, date))
+            .await
+            && key.currency_codes().iter().all(|currency| {
+                record
+                    .snapshot
+                    .rates
+                    .iter()
+                    .any(|rate| &rate.currency == currency)
+            })
+        {
+            return Some(record.snapshot);
+        }
+
+        self.database
+            .get_latest_exchange_rates(&period_cache_key(key, date))
+            .await
+            .ok()
+            .flatten()
+            .map(|record| record.snapshot)
+    }
+
+    async fn store_period(&self, key: &ExrKey, snapshot: &ExchangeRatesSnapshot) -> Result<()> {
+        let record = ExchangeRateRecord::new(
+            snapshot.clone(),
+            self.clock.now(),
+            period_cache_key(key, snapshot.observation_date),
+        );
+
+        self.database.store_exchange_rates(record).await?;
+
+        Ok(())
     }
 
-    fn get_rate_from_eur(&self, snapshot: &ExchangeRatesSnapshot, currency: &str) -> Result<f64> {
-        snapshot
+    /// The `CL_CURRENCY` codelist, fetched once and read from storage after
+    /// that.
+    async fn currency_codelist(&self) -> Option<Codelist> {
+        if let Ok(Some(codelist)) = self.database.get_codelist(CURRENCY_CODELIST).await {
+            return Some(codelist);
+        }
+
+        let codelist = self.client.fetch_codelist(CURRENCY_CODELIST).await.ok()?;
+
+        // Failing to store it only means fetching it again next time.
+        let _ = self.database.store_codelist(&codelist).await;
+
+        Some(codelist)
+    }
+
+    /// The ECB reference rate of `currency`, quoted as EUR/`currency`, and
+    /// the status of its observation.
+    fn reference_rate(
+        &self,
+        snapshot: &ExchangeRatesSnapshot,
+        currency: &str,
+    ) -> Result<(Rate, ObservationStatus)> {
+        if currency == "EUR" {
+            return Ok((
+                Rate::identity("EUR", snapshot.observation_date),
+                ObservationStatus::default(),
+            ));
+        }
+
+        let rate = snapshot
             .rates
             .iter()
             .find(|rate| rate.currency == currency)
-            .map(|rate| rate.rate)
-            .ok_or_else(|| anyhow!("Currency {} not found in snapshot", currency))
+            .ok_or_else(|| ProviderError::UnknownCurrency(currency.to_string()))?;
+
+        let value = rate.rate.ok_or_else(|| ProviderError::MissingRate {
+            currency: currency.to_string(),
+            date: rate.observation_date,
+            status: rate.status.clone(),
+        })?;
+
+        Ok((
+            Rate::new("EUR", currency, value, rate.observation_date),
+            rate.status.clone(),
+        ))
+    }
+}
+
+/// Normalizes a currency code as given by a caller, e.g. `usd`, into the
+/// upper-case code the ECB quotes, e.g. `USD`.
+fn currency_code(currency: &str) -> Result<String> {
+    // Anything that is not a plain code cannot be quoted and would only
+    // make a series key invalid.
+    if currency.is_empty() || !currency.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(ProviderError::UnknownCurrency(currency.to_string()));
+    }
+
+    Ok(currency.to_ascii_uppercase())
+}
+
+/// Key of `series` restricted to the non-EUR `currencies`, deduplicated and
+/// sorted so that any order of the same pair shares a cache entry.
+fn quoted_key(series: ExrSeries, currencies: &[&str]) -> ExrKey {
+    let mut currencies = currencies
+        .iter()
+        .filter(|currency| **currency != "EUR")
+        .map(|currency| currency.to_string())
+        .collect::<Vec<_>>();
+    currencies.sort();
+    currencies.dedup();
+
+    ExrKey::new(series).currencies(currencies)
+}
+
+fn period_cache_key(key: &ExrKey, date: NaiveDate) -> String {
+    format!(
+        "{}/{}",
+        key.key(),
+        key.series().frequency.format_period(date)
+    )
+}
+
+fn cache_validators(metadata: &HashMap<String, String>) -> CacheValidators {
+    CacheValidators {
+        etag: metadata.get(ETAG_METADATA_KEY).cloned(),
+        last_modified: metadata.get(LAST_MODIFIED_METADATA_KEY).cloned(),
     }
 }
+
+fn validators_metadata(validators: CacheValidators) -> HashMap<String, String> {
+    [
+        (ETAG_METADATA_KEY, validators.etag),
+        (LAST_MODIFIED_METADATA_KEY, validators.last_modified),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| Some((key.to_string(), value?)))
+    .collect()
+}
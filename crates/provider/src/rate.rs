@@ -0,0 +1,66 @@
+//! Quotation model the provider converts with. A [`Rate`] reads like a
+//! currency pair: EUR/USD at 1.0807 means one euro buys 1.0807 US dollars,
+//! which is how the ECB publishes its reference rates.
+
+use chrono::NaiveDate;
+
+/// Price of one unit of `base` in units of `quote`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rate {
+    pub base: String,
+    pub quote: String,
+    pub value: f64,
+    /// Fixing the rate was observed at, or the oldest of the fixings it was
+    /// derived from.
+    pub date: NaiveDate,
+}
+
+impl Rate {
+    pub fn new(
+        base: impl Into<String>,
+        quote: impl Into<String>,
+        value: f64,
+        date: NaiveDate,
+    ) -> Self {
+        Self {
+            base: base.into(),
+            quote: quote.into(),
+            value,
+            date,
+        }
+    }
+
+    /// `currency` priced in itself, at 1.
+    pub fn identity(currency: impl Into<String>, date: NaiveDate) -> Self {
+        let currency = currency.into();
+        Self::new(currency.clone(), currency, 1.0, date)
+    }
+
+    /// The same quotation seen from the other side: EUR/USD becomes USD/EUR.
+    pub fn invert(&self) -> Self {
+        Self::new(
+            self.quote.clone(),
+            self.base.clone(),
+            1.0 / self.value,
+            self.date,
+        )
+    }
+
+    /// Cross rate between the quote currencies of two rates sharing the same
+    /// base: EUR/USD crossed with EUR/JPY gives USD/JPY.
+    pub fn cross(&self, other: &Rate) -> Self {
+        debug_assert_eq!(self.base, other.base, "crossed rates must share their base");
+
+        Self::new(
+            self.quote.clone(),
+            other.quote.clone(),
+            other.value / self.value,
+            self.date.min(other.date),
+        )
+    }
+
+    /// Converts `amount` of `base` into `quote`.
+    pub fn convert(&self, amount: f64) -> f64 {
+        amount * self.value
+    }
+}
@@ -0,0 +1,181 @@
+//! Background task keeping the stored latest reference rates current, so that
+//! the first conversion after a publication does not wait for the ECB.
+
+use std::{
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use chrono::{NaiveDate, NaiveTime};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use crate::{
+    AlwaysRefresh, BceExchangeProvider, LATEST_CACHE_KEY,
+    calendar::{self, PUBLICATION_TIME},
+    clock::Clock,
+};
+
+/// When the background refresh wakes up and how it retries.
+#[derive(Debug, Clone, Copy)]
+pub struct RefreshSchedule {
+    /// Time, in Paris, at which the ECB is expected to publish.
+    pub publication_time: NaiveTime,
+    /// Delay after the publication time before the first attempt.
+    pub delay: Duration,
+    /// Wait before retrying while the new fixing has not appeared, doubled
+    /// after every attempt.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+/// A running background refresh, stopped when dropped.
+pub(crate) struct RefreshTask {
+    shutdown: oneshot::Sender<()>,
+    handle: JoinHandle<()>,
+}
+
+impl Default for RefreshSchedule {
+    fn default() -> Self {
+        Self {
+            publication_time: PUBLICATION_TIME,
+            delay: Duration::from_secs(5 * 60),
+            initial_backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+impl BceExchangeProvider {
+    /// Starts a tokio task refreshing the stored latest rates shortly after
+    /// every ECB publication, retrying with backoff until the new fixing
+    /// appears. A fixing already due but not stored yet is fetched right
+    /// away. Replaces any background refresh already running.
+    ///
+    /// The task only holds a weak reference to the provider and stops when
+    /// the provider is dropped or [`stop_background_refresh`] is called.
+    /// Nothing is started under a cache policy whose rates never expire,
+    /// such as [`NeverRefresh`](crate::NeverRefresh).
+    ///
+    /// [`stop_background_refresh`]: Self::stop_background_refresh
+    pub fn start_background_refresh(self: &Arc<Self>, schedule: RefreshSchedule) {
+        if !self.cache_policy.expires() {
+            return;
+        }
+
+        let (shutdown, shutdown_requested) = oneshot::channel();
+
+        let handle = tokio::spawn(run(
+            Arc::downgrade(self),
+            self.clock.clone(),
+            schedule,
+            shutdown_requested,
+        ));
+
+        let previous = self
+            .refresh_task
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .replace(RefreshTask { shutdown, handle });
+
+        // Dropping the previous task's sender stops it.
+        drop(previous);
+    }
+
+    /// Stops the background refresh, abandoning a refresh in progress, and
+    /// waits for its task to end.
+    pub async fn stop_background_refresh(&self) {
+        let task = self
+            .refresh_task
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+
+        if let Some(RefreshTask { shutdown, handle }) = task {
+            let _ = shutdown.send(());
+            let _ = handle.await;
+        }
+    }
+
+    /// Date of the latest fixing in storage.
+    async fn stored_fixing(&self) -> Option<NaiveDate> {
+        self.database
+            .get_latest_exchange_rates(LATEST_CACHE_KEY)
+            .await
+            .ok()
+            .flatten()
+            .map(|record| record.snapshot.observation_date)
+    }
+
+    /// Fetches the latest rates regardless of the cache policy. Returns the
+    /// date of the stored fixing, or `None` when the ECB could not be
+    /// reached.
+    async fn refresh_latest(&self) -> Option<NaiveDate> {
+        match self.fetch_latest_with(&AlwaysRefresh).await {
+            Ok((snapshot, None)) => Some(snapshot.observation_date),
+            Ok((_, Some(_))) | Err(_) => None,
+        }
+    }
+}
+
+async fn run(
+    provider: Weak<BceExchangeProvider>,
+    clock: Arc<dyn Clock>,
+    schedule: RefreshSchedule,
+    mut shutdown: oneshot::Receiver<()>,
+) {
+    let Some(initial) = provider.upgrade() else {
+        return;
+    };
+    let mut stored = initial.stored_fixing().await;
+    drop(initial);
+
+    let mut backoff = schedule.initial_backoff;
+
+    loop {
+        let expected = calendar::last_fixing_date(clock.now(), schedule.publication_time);
+
+        if stored.is_none_or(|stored| stored < expected) {
+            let Some(provider) = provider.upgrade() else {
+                return;
+            };
+            // The ECB requests retry for a while, which must not hold up a
+            // shutdown.
+            let refreshed = tokio::select! {
+                fixing = provider.refresh_latest() => fixing,
+                _ = &mut shutdown => return,
+            };
+            if let Some(fixing) = refreshed {
+                stored = Some(fixing);
+            }
+            drop(provider);
+
+            // The ECB is late or unreachable: try again, moving on to the
+            // next fixing should it become due in the meantime.
+            if stored.is_none_or(|stored| stored < expected) {
+                if !sleep(backoff, &mut shutdown).await {
+                    return;
+                }
+                backoff = backoff.saturating_mul(2).min(schedule.max_backoff);
+                continue;
+            }
+        }
+
+        backoff = schedule.initial_backoff;
+
+        let now = clock.now();
+        let next_publication = calendar::next_publication(now, schedule.publication_time);
+        let wait = (next_publication - now).to_std().unwrap_or_default() + schedule.delay;
+        if !sleep(wait, &mut shutdown).await {
+            return;
+        }
+    }
+}
+
+/// Sleeps for `duration`. Returns `false` when a shutdown was requested, or
+/// the provider dropped, in the meantime.
+async fn sleep(duration: Duration, shutdown: &mut oneshot::Receiver<()>) -> bool {
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => true,
+        _ = shutdown => false,
+    }
+}
@@ -0,0 +1,96 @@
+//! Incremental sync of a locally stored EXR history, using the ECB's
+//! `updatedAfter` parameter to download only what changed since the last run.
+
+use chrono::NaiveDate;
+
+use bce_exchange_database::ExchangeRateRecord;
+
+use crate::{BceExchangeProvider, ExchangeRatesSnapshot, ExrSeries, Result};
+
+/// What a [`BceExchangeProvider::sync_history`] run changed.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// `true` when there was no previous sync and the history was downloaded
+    /// from scratch.
+    pub initial: bool,
+    /// Number of periods whose stored snapshot was created or updated.
+    pub periods: usize,
+    /// Number of observations applied, revisions included.
+    pub observations: usize,
+}
+
+impl BceExchangeProvider {
+    /// Brings the stored history of `series` up to date.
+    ///
+    /// The first run downloads every period since `since`; later runs only ask
+    /// the ECB for observations added or revised since the previous
+    /// successful run and merge them into the stored periods. The sync time
+    /// is recorded only once every delta has been stored, so a failed run is
+    /// simply repeated by the next one.
+    pub async fn sync_history(&self, series: ExrSeries, since: NaiveDate) -> Result<SyncReport> {
+        let sync_identifier = format!("sync/{}", series.key());
+        // Taken before the request so that revisions published while it is in
+        // flight are picked up by the next run.
+        let started_at = self.clock.now();
+
+        let last_sync = self.database.get_last_sync(&sync_identifier).await?;
+
+        let snapshots = match last_sync {
+            Some(last_sync) => self.client.fetch_updated_after(series, last_sync).await?,
+            None => {
+                self.client
+                    .fetch_series_between(series, since, started_at.date_naive())
+                    .await?
+            }
+        };
+
+        let mut report = SyncReport {
+            initial: last_sync.is_none(),
+            ..SyncReport::default()
+        };
+
+        for snapshot in snapshots {
+            report.periods += 1;
+            report.observations += snapshot.rates.len();
+
+            self.merge_into_history(series, snapshot).await?;
+        }
+
+        self.database
+            .store_last_sync(&sync_identifier, started_at)
+            .await?;
+
+        Ok(report)
+    }
+
+    /// Merges `snapshot`, the observations of a single period, into the
+    /// stored history of `series`.
+    pub(crate) async fn merge_into_history(
+        &self,
+        series: ExrSeries,
+        snapshot: ExchangeRatesSnapshot,
+    ) -> Result<()> {
+        let cache_key = history_cache_key(series, snapshot.observation_date);
+
+        let snapshot = match self.database.get_latest_exchange_rates(&cache_key).await? {
+            Some(record) => record.snapshot.merged_with(snapshot),
+            None => snapshot,
+        };
+
+        self.database
+            .store_exchange_rates(ExchangeRateRecord::new(
+                snapshot,
+                self.clock.now(),
+                cache_key,
+            ))
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Storage key of the snapshot holding every currency's observation of
+/// `series` for the period containing `date`.
+pub(crate) fn history_cache_key(series: ExrSeries, date: NaiveDate) -> String {
+    format!("{}/{}", series.key(), series.frequency.format_period(date))
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use bce_exchange_provider::{
+    AlwaysRefresh, CachePolicy, FixedTtl, NeverRefresh, PublicationSchedule,
+};
+use bce_exchange_test_support::date;
+use chrono::{DateTime, TimeDelta, Utc};
+
+fn instant(rfc3339: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()
+}
+
+#[test]
+fn publication_schedule_waits_for_the_next_fixing() {
+    let policy = PublicationSchedule::default();
+    let friday = date(2025, 3, 28);
+    let fetched_at = instant("2025-03-28T16:30:00+01:00");
+
+    // Nothing new until Monday 16:00.
+    assert!(policy.is_fresh(friday, fetched_at, instant("2025-03-30T12:00:00+02:00")));
+    assert!(policy.is_fresh(friday, fetched_at, instant("2025-03-31T15:59:00+02:00")));
+    assert!(!policy.is_fresh(friday, fetched_at, instant("2025-03-31T16:00:00+02:00")));
+}
+
+#[test]
+fn publication_schedule_rechecks_a_late_fixing_periodically() {
+    let policy = PublicationSchedule::default();
+    let thursday = date(2025, 3, 27);
+    let fetched_at = instant("2025-03-28T16:05:00+01:00");
+
+    assert!(policy.is_fresh(thursday, fetched_at, instant("2025-03-28T16:10:00+01:00")));
+    assert!(!policy.is_fresh(thursday, fetched_at, instant("2025-03-28T16:20:00+01:00")));
+}
+
+#[test]
+fn publication_time_is_configurable() {
+    let policy = PublicationSchedule {
+        publication_time: chrono::NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        late_recheck: TimeDelta::zero(),
+    };
+    let thursday = date(2025, 3, 27);
+    let fetched_at = instant("2025-03-28T10:00:00+01:00");
+
+    assert!(policy.is_fresh(thursday, fetched_at, instant("2025-03-28T16:30:00+01:00")));
+    assert!(!policy.is_fresh(thursday, fetched_at, instant("2025-03-28T17:00:00+01:00")));
+}
+
+#[test]
+fn fixed_ttl_counts_from_the_fetch() {
+    let policy = FixedTtl(Duration::from_secs(3600));
+    let fetched_at = instant("2025-03-28T10:00:00Z");
+    let friday = date(2025, 3, 28);
+
+    assert!(policy.is_fresh(friday, fetched_at, instant("2025-03-28T10:59:59Z")));
+    assert!(!policy.is_fresh(friday, fetched_at, instant("2025-03-28T11:00:00Z")));
+}
+
+#[test]
+fn never_and_always_refresh_ignore_dates() {
+    let friday = date(2025, 3, 28);
+    let fetched_at = instant("2025-03-28T16:30:00Z");
+    let much_later = instant("2026-03-28T16:30:00Z");
+
+    assert!(NeverRefresh.is_fresh(friday, fetched_at, much_later));
+    assert!(!AlwaysRefresh.is_fresh(friday, fetched_at, fetched_at));
+}
@@ -0,0 +1,102 @@
+use bce_exchange_provider::calendar::{
+    PUBLICATION_TIME, easter_sunday, is_publication_day, is_target_holiday, last_fixing_date,
+    next_publication, next_publication_day, previous_publication_day,
+};
+use bce_exchange_test_support::date;
+use chrono::DateTime;
+
+fn instant(rfc3339: &str) -> DateTime<chrono::FixedOffset> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap()
+}
+
+#[test]
+fn computes_easter_sunday() {
+    assert_eq!(easter_sunday(1818), Some(date(1818, 3, 22)));
+    assert_eq!(easter_sunday(2000), Some(date(2000, 4, 23)));
+    assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+    assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+    assert_eq!(easter_sunday(2026), Some(date(2026, 4, 5)));
+    assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+}
+
+#[test]
+fn knows_target_closing_days() {
+    for holiday in [
+        date(2025, 1, 1),
+        date(2025, 4, 18),
+        date(2025, 4, 21),
+        date(2025, 5, 1),
+        date(2025, 12, 25),
+        date(2025, 12, 26),
+    ] {
+        assert!(is_target_holiday(holiday), "{holiday}");
+        assert!(!is_publication_day(holiday), "{holiday}");
+    }
+
+    assert!(!is_target_holiday(date(2025, 4, 20)));
+    assert!(!is_target_holiday(date(2025, 12, 24)));
+    assert!(is_publication_day(date(2025, 12, 24)));
+    assert!(!is_publication_day(date(2025, 3, 29)));
+}
+
+#[test]
+fn finds_surrounding_publication_days() {
+    assert_eq!(
+        previous_publication_day(date(2025, 4, 21)),
+        date(2025, 4, 17)
+    );
+    assert_eq!(next_publication_day(date(2025, 4, 18)), date(2025, 4, 22));
+    assert_eq!(next_publication_day(date(2025, 12, 25)), date(2025, 12, 29));
+    assert_eq!(
+        previous_publication_day(date(2025, 3, 28)),
+        date(2025, 3, 28)
+    );
+}
+
+#[test]
+fn last_fixing_follows_the_publication_schedule() {
+    // Before and after the publication on a Friday, Paris being UTC+1.
+    assert_eq!(
+        last_fixing_date(instant("2025-03-28T14:59:00Z"), PUBLICATION_TIME),
+        date(2025, 3, 27)
+    );
+    assert_eq!(
+        last_fixing_date(instant("2025-03-28T15:00:00Z"), PUBLICATION_TIME),
+        date(2025, 3, 28)
+    );
+    // Over the weekend and on Monday morning, once summer time started.
+    assert_eq!(
+        last_fixing_date(instant("2025-03-30T20:00:00Z"), PUBLICATION_TIME),
+        date(2025, 3, 28)
+    );
+    assert_eq!(
+        last_fixing_date(instant("2025-03-31T09:00:00+02:00"), PUBLICATION_TIME),
+        date(2025, 3, 28)
+    );
+    // Easter Monday evening still points to Maundy Thursday.
+    assert_eq!(
+        last_fixing_date(instant("2025-04-21T18:00:00+02:00"), PUBLICATION_TIME),
+        date(2025, 4, 17)
+    );
+    // New Year's Day, whatever the time zone of the instant.
+    assert_eq!(
+        last_fixing_date(instant("2026-01-01T23:30:00-05:00"), PUBLICATION_TIME),
+        date(2025, 12, 31)
+    );
+}
+
+#[test]
+fn next_publication_skips_closing_days() {
+    assert_eq!(
+        next_publication(instant("2025-03-28T15:59:00+01:00"), PUBLICATION_TIME),
+        instant("2025-03-28T16:00:00+01:00")
+    );
+    assert_eq!(
+        next_publication(instant("2025-03-28T16:00:00+01:00"), PUBLICATION_TIME),
+        instant("2025-03-31T16:00:00+02:00")
+    );
+    assert_eq!(
+        next_publication(instant("2025-04-17T18:00:00+02:00"), PUBLICATION_TIME),
+        instant("2025-04-22T16:00:00+02:00")
+    );
+}
@@ -0,0 +1,463 @@
+use std::{sync::Arc, time::Duration};
+
+use bce_exchange_provider::{
+    AlwaysRefresh, FallbackPolicy, FixedTtl, NeverRefresh, ObservationStatus, ProviderError,
+};
+use bce_exchange_test_support::{
+    DATA_API, ManualClock, MockHttpClient, MockResponse, fixtures, provider,
+};
+use chrono::{NaiveDate, TimeDelta, Utc};
+
+const CODELIST_CURRENCY: &str = "/codelist/ECB/CL_CURRENCY";
+const EUROFXREF_DAILY: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml";
+
+#[tokio::test]
+async fn reports_fixing_and_series_of_a_conversion() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let conversion = provider(&http_client)
+        .rate_conversion("USD", "JPY")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        conversion.rate.date,
+        NaiveDate::from_ymd_opt(2025, 3, 28).unwrap()
+    );
+    assert!(conversion.status.is_normal());
+    assert_eq!(conversion.from_series.unwrap().currency, "USD");
+    assert_eq!(conversion.to_series.unwrap().decimals, Some(2));
+}
+
+#[tokio::test]
+async fn currency_codes_are_case_insensitive() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::week())
+        .respond(DATA_API, fixtures::normal_day());
+    let provider = provider(&http_client);
+
+    let friday = NaiveDate::from_ymd_opt(2025, 3, 28).unwrap();
+    let conversion = provider
+        .rate_conversion_on("eur", "Usd", friday)
+        .await
+        .unwrap();
+    assert_eq!(conversion.rate.value, 1.0807);
+    assert!(http_client.requests()[0].url.contains("D.USD.EUR.SP00.A?"));
+
+    let conversion = provider.rate_conversion("usd", "eur").await.unwrap();
+    assert_eq!(conversion.rate.base, "USD");
+    assert_eq!(conversion.rate.quote, "EUR");
+    assert_eq!(conversion.rate.value, 1.0 / 1.0807);
+}
+
+#[tokio::test]
+async fn quoted_currencies_skip_discontinued_series() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::discontinued_series())
+        .respond(CODELIST_CURRENCY, fixtures::codelist_currency());
+
+    let currencies = provider(&http_client).quoted_currencies().await.unwrap();
+
+    let codes: Vec<&str> = currencies.iter().map(|code| code.id.as_str()).collect();
+    assert_eq!(codes, ["CHF", "EUR", "GBP", "JPY", "USD"]);
+}
+
+#[tokio::test]
+async fn currency_codelist_is_stored() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::normal_day())
+        .respond(CODELIST_CURRENCY, fixtures::codelist_currency());
+    let provider = provider(&http_client);
+
+    provider.quoted_currencies().await.unwrap();
+    let currencies = provider.quoted_currencies().await.unwrap();
+
+    assert_eq!(currencies[0].name.as_deref(), Some("Swiss franc"));
+    let codelist_requests = http_client
+        .requests()
+        .iter()
+        .filter(|request| request.url.contains(CODELIST_CURRENCY))
+        .count();
+    assert_eq!(codelist_requests, 1);
+}
+
+#[tokio::test]
+async fn unknown_currency_is_reported() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let error = provider(&http_client)
+        .rate_conversion("XYZ", "EUR")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(&error, ProviderError::UnknownCurrency(currency) if currency == "XYZ"),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn latest_rates_are_cached() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    let provider = provider(&http_client);
+
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+    provider.rate_conversion("GBP", "EUR").await.unwrap();
+
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn latest_fixing_stays_addressable_by_its_date() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    let provider = provider(&http_client);
+
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+    let friday = NaiveDate::from_ymd_opt(2025, 3, 28).unwrap();
+    let conversion = provider
+        .rate_conversion_on("EUR", "GBP", friday)
+        .await
+        .unwrap();
+
+    assert_eq!(conversion.rate.date, friday);
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn cache_policy_is_selectable() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+
+    let always = provider(&http_client).with_cache_policy(AlwaysRefresh);
+    always.rate_conversion("USD", "EUR").await.unwrap();
+    always.rate_conversion("USD", "EUR").await.unwrap();
+    assert_eq!(http_client.requests().len(), 2);
+
+    // The fixture's 2025 fixing is long outdated, yet never refreshed.
+    let never = provider(&http_client).with_cache_policy(NeverRefresh);
+    never.rate_conversion("USD", "EUR").await.unwrap();
+    never.rate_conversion("USD", "EUR").await.unwrap();
+    assert_eq!(http_client.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn not_modified_renews_the_stored_rates() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(
+            DATA_API,
+            fixtures::normal_day().with_header("ETag", "\"exr-20250328\""),
+        )
+        .respond(DATA_API, MockResponse::new(304, ""));
+    let clock = Arc::new(ManualClock::new(Utc::now()));
+    let provider = provider(&http_client)
+        .with_clock(clock.clone())
+        .with_cache_policy(FixedTtl(Duration::from_secs(3600)));
+
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+    clock.advance(TimeDelta::hours(2));
+    let revalidated = provider.rate_conversion("USD", "EUR").await.unwrap();
+    assert!(revalidated.stale.is_none());
+    assert_eq!(revalidated.rate.value, 1.0 / 1.0807);
+
+    // The 304 restarted the TTL, so half an hour later nothing is fetched.
+    clock.advance(TimeDelta::minutes(30));
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+
+    let requests = http_client.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[1].header("If-None-Match"),
+        Some("\"exr-20250328\"")
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn falls_back_to_eurofxref_when_the_data_api_fails() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::server_error())
+        .respond(EUROFXREF_DAILY, fixtures::eurofxref_daily());
+
+    let conversion = provider(&http_client)
+        .rate_conversion("CHF", "EUR")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        conversion.rate.date,
+        NaiveDate::from_ymd_opt(2025, 3, 28).unwrap()
+    );
+    assert!(
+        http_client
+            .requests()
+            .iter()
+            .any(|request| request.url == EUROFXREF_DAILY)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn serves_stale_rates_when_both_sources_fail() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::normal_day())
+        .respond(DATA_API, fixtures::server_error())
+        .respond(EUROFXREF_DAILY, fixtures::server_error());
+    let provider = provider(&http_client).with_cache_policy(AlwaysRefresh);
+
+    let fresh = provider.rate_conversion("USD", "EUR").await.unwrap();
+    assert!(fresh.stale.is_none());
+
+    let conversion = provider.rate_conversion("USD", "EUR").await.unwrap();
+
+    let stale = conversion.stale.unwrap();
+    assert!(
+        stale
+            .reason
+            .contains("ECB data API and eurofxref fallback both failed")
+    );
+    assert_eq!(
+        conversion.rate.date,
+        NaiveDate::from_ymd_opt(2025, 3, 28).unwrap()
+    );
+    assert_eq!(conversion.rate.value, 1.0 / 1.0807);
+}
+
+#[tokio::test(start_paused = true)]
+async fn serves_a_stored_fixing_of_any_date_when_both_sources_fail() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::week())
+        .respond(DATA_API, fixtures::server_error())
+        .respond(EUROFXREF_DAILY, fixtures::server_error());
+    let clock = Arc::new(ManualClock::new(Utc::now()));
+    let provider = provider(&http_client).with_clock(clock.clone());
+    let friday = NaiveDate::from_ymd_opt(2025, 3, 28).unwrap();
+
+    // Only the fixings of a conversion on a date are stored.
+    provider
+        .rate_conversion_on("EUR", "USD", friday)
+        .await
+        .unwrap();
+
+    let conversion = provider.rate_conversion("EUR", "USD").await.unwrap();
+    assert!(conversion.stale.is_some());
+    assert_eq!(conversion.rate.date, friday);
+    assert_eq!(conversion.rate.value, 1.0807);
+
+    clock.advance(TimeDelta::days(8));
+    let error = provider.rate_conversion("EUR", "USD").await.unwrap_err();
+    assert!(
+        matches!(error, ProviderError::Unavailable { .. }),
+        "{error:?}"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn stale_rates_can_be_refused() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::normal_day())
+        .respond(DATA_API, fixtures::server_error())
+        .respond(EUROFXREF_DAILY, fixtures::server_error());
+    let provider = provider(&http_client)
+        .with_cache_policy(AlwaysRefresh)
+        .with_max_staleness(None);
+
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+    let error = provider.rate_conversion("USD", "EUR").await.unwrap_err();
+
+    assert!(
+        matches!(error, ProviderError::Unavailable { .. }),
+        "{error:?}"
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn unavailable_when_both_sources_fail() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::server_error())
+        .respond(EUROFXREF_DAILY, fixtures::server_error());
+
+    let error = provider(&http_client)
+        .rate_conversion("USD", "EUR")
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ProviderError::Unavailable { .. }),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn missing_observation_is_reported() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::missing_observation());
+    let friday = NaiveDate::from_ymd_opt(2025, 3, 28).unwrap();
+
+    let error = provider(&http_client)
+        .rate_conversion_on("JPY", "EUR", friday)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            &error,
+            ProviderError::MissingRate { currency, date, status: ObservationStatus::Missing }
+                if currency == "JPY" && *date == friday
+        ),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn period_without_fixing_is_reported() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::weekend());
+    let saturday = NaiveDate::from_ymd_opt(2025, 3, 29).unwrap();
+
+    let error = provider(&http_client)
+        .rate_conversion_for_period("USD", "EUR", Default::default(), saturday)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ProviderError::NoDataForDate(date) if date == saturday),
+        "{error:?}"
+    );
+    assert!(
+        http_client.requests()[0]
+            .url
+            .starts_with(&format!("{}D.USD.EUR.SP00.A?", DATA_API))
+    );
+}
+
+#[tokio::test]
+async fn converts_in_the_direction_asked() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    let provider = provider(&http_client);
+
+    let eur_usd = provider.rate_conversion("EUR", "USD").await.unwrap().rate;
+    assert_eq!(
+        (eur_usd.base.as_str(), eur_usd.quote.as_str()),
+        ("EUR", "USD")
+    );
+    assert_eq!(eur_usd.value, 1.0807);
+
+    let usd_eur = provider.rate_conversion("USD", "EUR").await.unwrap().rate;
+    assert_eq!(usd_eur.value, 1.0 / 1.0807);
+
+    let usd_jpy = provider.rate_conversion("USD", "JPY").await.unwrap().rate;
+    assert_eq!(usd_jpy.value, 162.55 / 1.0807);
+    assert!((usd_jpy.convert(100.0) - 15041.18).abs() < 0.01);
+
+    let eur_eur = provider.rate_conversion("EUR", "EUR").await.unwrap().rate;
+    assert_eq!(eur_eur.value, 1.0);
+}
+
+#[tokio::test]
+async fn weekend_conversion_uses_the_previous_fixing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::week());
+    let provider = provider(&http_client);
+    let sunday = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+
+    let conversion = provider
+        .rate_conversion_on("EUR", "USD", sunday)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        conversion.rate.date,
+        NaiveDate::from_ymd_opt(2025, 3, 28).unwrap()
+    );
+    assert_eq!(conversion.rate.value, 1.0807);
+    assert!(
+        http_client.requests()[0].url.contains(
+            "D.USD.EUR.SP00.A?format=jsondata&startPeriod=2025-03-23&endPeriod=2025-03-30"
+        )
+    );
+
+    // The fixings of the week were cached along the way.
+    let wednesday = NaiveDate::from_ymd_opt(2025, 3, 26).unwrap();
+    let conversion = provider
+        .rate_conversion_on("USD", "EUR", wednesday)
+        .await
+        .unwrap();
+    assert_eq!(conversion.rate.date, wednesday);
+    assert_eq!(conversion.rate.value, 1.0 / 1.0773);
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn weekend_conversion_is_cached() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::week());
+    let provider = provider(&http_client);
+    let saturday = NaiveDate::from_ymd_opt(2025, 3, 29).unwrap();
+
+    for _ in 0..2 {
+        let conversion = provider
+            .rate_conversion_on("EUR", "USD", saturday)
+            .await
+            .unwrap();
+        assert_eq!(
+            conversion.rate.date,
+            NaiveDate::from_ymd_opt(2025, 3, 28).unwrap()
+        );
+    }
+
+    assert_eq!(http_client.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn weekend_conversion_can_use_the_next_fixing() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::week());
+    let saturday = NaiveDate::from_ymd_opt(2025, 3, 22).unwrap();
+
+    let conversion = provider(&http_client)
+        .with_fallback_policy(FallbackPolicy::NextBusinessDay)
+        .rate_conversion_on("USD", "JPY", saturday)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        conversion.rate.date,
+        NaiveDate::from_ymd_opt(2025, 3, 24).unwrap()
+    );
+    assert_eq!(conversion.rate.value, 162.54 / 1.0824);
+    assert!(
+        http_client.requests()[0]
+            .url
+            .contains("startPeriod=2025-03-22&endPeriod=2025-03-29")
+    );
+}
+
+#[tokio::test]
+async fn strict_conversion_requires_a_fixing_on_the_date() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::weekend());
+    let saturday = NaiveDate::from_ymd_opt(2025, 3, 29).unwrap();
+
+    let error = provider(&http_client)
+        .with_fallback_policy(FallbackPolicy::Strict)
+        .rate_conversion_on("USD", "EUR", saturday)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, ProviderError::NoDataForDate(date) if date == saturday),
+        "{error:?}"
+    );
+}
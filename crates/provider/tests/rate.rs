@@ -0,0 +1,67 @@
+use bce_exchange_provider::Rate;
+use chrono::NaiveDate;
+use proptest::prelude::*;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+}
+
+/// A reference rate of `currency` against the euro, within the range of
+/// values the ECB publishes.
+fn reference_rate(currency: &'static str) -> impl Strategy<Value = Rate> {
+    (1e-4..1e5f64, 1..=28u32)
+        .prop_map(move |(value, day)| Rate::new("EUR", currency, value, date(day)))
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= expected.abs() * 1e-9,
+        "{actual} != {expected}"
+    );
+}
+
+proptest! {
+    #[test]
+    fn inverting_twice_is_the_identity(rate in reference_rate("USD")) {
+        let round_trip = rate.invert().invert();
+
+        prop_assert_eq!(&round_trip.base, &rate.base);
+        prop_assert_eq!(&round_trip.quote, &rate.quote);
+        prop_assert_eq!(round_trip.date, rate.date);
+        assert_close(round_trip.value, rate.value);
+    }
+
+    #[test]
+    fn converting_there_and_back_keeps_the_amount(
+        rate in reference_rate("USD"),
+        amount in 0.01..1e9f64,
+    ) {
+        assert_close(rate.invert().convert(rate.convert(amount)), amount);
+    }
+
+    #[test]
+    fn crossing_with_the_identity_inverts(rate in reference_rate("JPY")) {
+        let eur = Rate::identity("EUR", rate.date);
+
+        assert_close(eur.cross(&rate).value, rate.value);
+        assert_close(rate.cross(&eur).value, rate.invert().value);
+        prop_assert_eq!(&rate.cross(&eur).quote, "EUR");
+    }
+
+    #[test]
+    fn crossing_is_triangular(
+        usd in reference_rate("USD"),
+        jpy in reference_rate("JPY"),
+        gbp in reference_rate("GBP"),
+    ) {
+        let usd_jpy = usd.cross(&jpy);
+        let jpy_gbp = jpy.cross(&gbp);
+        let usd_gbp = usd.cross(&gbp);
+
+        prop_assert_eq!(&usd_jpy.base, "USD");
+        prop_assert_eq!(&usd_jpy.quote, "JPY");
+        assert_close(usd_jpy.value * jpy_gbp.value, usd_gbp.value);
+        assert_close(jpy.cross(&usd).value, usd_jpy.invert().value);
+        prop_assert_eq!(usd_jpy.date, usd.date.min(jpy.date));
+    }
+}
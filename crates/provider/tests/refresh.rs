@@ -0,0 +1,133 @@
+use std::{sync::Arc, time::Duration};
+
+use bce_exchange_provider::{Clock, NeverRefresh, RefreshSchedule};
+use bce_exchange_test_support::{DATA_API, MockHttpClient, fixtures, provider};
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+
+/// Wall clock starting at `origin` and moving with tokio's clock, which the
+/// tests pause and let advance whenever every task sleeps.
+struct TokioClock {
+    origin: DateTime<Utc>,
+    started: Instant,
+}
+
+impl TokioClock {
+    fn starting_at(rfc3339: &str) -> Self {
+        Self {
+            origin: instant(rfc3339),
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Clock for TokioClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.origin + self.started.elapsed()
+    }
+}
+
+fn instant(rfc3339: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc()
+}
+
+/// Sleeps until the simulated clock reads `rfc3339`.
+async fn advance_to(clock: &Arc<TokioClock>, rfc3339: &str) {
+    let left = (instant(rfc3339) - clock.now()).to_std().unwrap();
+    tokio::time::sleep(left).await;
+}
+
+fn data_requests(http_client: &MockHttpClient) -> usize {
+    http_client
+        .requests()
+        .iter()
+        .filter(|request| request.url.starts_with(DATA_API))
+        .count()
+}
+
+#[tokio::test(start_paused = true)]
+async fn refreshes_after_each_publication() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    // Friday 2025-03-28, before the publication.
+    let clock = Arc::new(TokioClock::starting_at("2025-03-28T12:00:00+01:00"));
+    let provider = Arc::new(provider(&http_client).with_clock(clock.clone()));
+
+    provider.start_background_refresh(RefreshSchedule::default());
+
+    // Nothing stored yet, so the latest fixing is fetched right away. The
+    // fixture already holds Friday's.
+    advance_to(&clock, "2025-03-28T12:01:00+01:00").await;
+    assert_eq!(data_requests(&http_client), 1);
+
+    // Nothing new is due on Friday afternoon nor over the weekend.
+    advance_to(&clock, "2025-03-31T16:04:00+02:00").await;
+    assert_eq!(data_requests(&http_client), 1);
+
+    // Monday's fixing is asked for shortly after its publication.
+    advance_to(&clock, "2025-03-31T16:05:30+02:00").await;
+    assert_eq!(data_requests(&http_client), 2);
+
+    // It does not appear, as the fixture stays on Friday: retry after 1, 2, 4
+    // and 8 minutes.
+    advance_to(&clock, "2025-03-31T16:20:30+02:00").await;
+    assert_eq!(data_requests(&http_client), 6);
+
+    // The conversion is answered from what the refresh stored.
+    provider.rate_conversion("USD", "EUR").await.unwrap();
+
+    provider.stop_background_refresh().await;
+    let requests = data_requests(&http_client);
+
+    advance_to(&clock, "2025-04-01T18:00:00+02:00").await;
+    assert_eq!(data_requests(&http_client), requests);
+}
+
+#[tokio::test(start_paused = true)]
+async fn stops_when_the_provider_is_dropped() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    let clock = Arc::new(TokioClock::starting_at("2025-03-28T12:00:00+01:00"));
+    let provider = Arc::new(provider(&http_client).with_clock(clock.clone()));
+
+    provider.start_background_refresh(RefreshSchedule::default());
+    advance_to(&clock, "2025-03-28T12:01:00+01:00").await;
+    drop(provider);
+
+    tokio::time::sleep(Duration::from_secs(7 * 24 * 60 * 60)).await;
+    assert_eq!(data_requests(&http_client), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn not_started_when_rates_never_expire() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::normal_day());
+    let clock = Arc::new(TokioClock::starting_at("2025-03-28T12:00:00+01:00"));
+    let provider = Arc::new(
+        provider(&http_client)
+            .with_clock(clock.clone())
+            .with_cache_policy(NeverRefresh),
+    );
+
+    provider.start_background_refresh(RefreshSchedule::default());
+
+    advance_to(&clock, "2025-03-31T18:00:00+02:00").await;
+    assert_eq!(data_requests(&http_client), 0);
+}
+
+#[tokio::test(start_paused = true)]
+async fn stop_abandons_a_refresh_in_progress() {
+    let http_client = MockHttpClient::new();
+    http_client.respond(DATA_API, fixtures::server_error());
+    let clock = Arc::new(TokioClock::starting_at("2025-03-28T17:00:00+01:00"));
+    let provider = Arc::new(provider(&http_client).with_clock(clock.clone()));
+
+    provider.start_background_refresh(RefreshSchedule::default());
+    // The first attempt has failed and is waiting to be retried.
+    tokio::task::yield_now().await;
+    assert_eq!(data_requests(&http_client), 1);
+
+    let stopped_at = Instant::now();
+    provider.stop_background_refresh().await;
+    assert_eq!(stopped_at.elapsed(), Duration::ZERO);
+}
@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use bce_exchange_provider::ExrSeries;
+use bce_exchange_test_support::{DATA_API, ManualClock, MockHttpClient, date, fixtures, provider};
+use chrono::TimeDelta;
+
+#[tokio::test]
+async fn later_syncs_ask_for_updates_since_the_previous_one() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::week())
+        .respond(DATA_API, fixtures::revision());
+    let clock = Arc::new(ManualClock::at("2025-03-28T17:00:00Z"));
+    let provider = provider(&http_client).with_clock(clock.clone());
+
+    let report = provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+    assert!(report.initial);
+    assert_eq!((report.periods, report.observations), (5, 10));
+
+    clock.advance(TimeDelta::hours(1));
+    let report = provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+    assert!(!report.initial);
+    assert_eq!((report.periods, report.observations), (1, 1));
+
+    clock.advance(TimeDelta::hours(1));
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+
+    let requests = http_client.requests();
+    assert!(
+        requests[0]
+            .url
+            .contains("D..EUR.SP00.A?format=jsondata&startPeriod=2025-03-24&endPeriod=2025-03-28")
+    );
+    assert!(
+        requests[1]
+            .url
+            .ends_with("updatedAfter=2025-03-28T17%3A00%3A00%2B00%3A00")
+    );
+    assert!(
+        requests[2]
+            .url
+            .ends_with("updatedAfter=2025-03-28T18%3A00%3A00%2B00%3A00")
+    );
+}
+
+#[tokio::test]
+async fn revisions_are_merged_into_the_stored_history() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::week())
+        .respond(DATA_API, fixtures::revision());
+    let clock = Arc::new(ManualClock::at("2025-03-28T17:00:00Z"));
+    let provider = provider(&http_client).with_clock(clock.clone());
+
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+    clock.advance(TimeDelta::hours(1));
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+
+    // Both conversions are answered from the synced history: the revised USD
+    // fixing replaced the original one, JPY was left as it was.
+    let friday = date(2025, 3, 28);
+    let usd = provider
+        .rate_conversion_on("EUR", "USD", friday)
+        .await
+        .unwrap();
+    assert_eq!(usd.rate.value, 1.0812);
+    let jpy = provider
+        .rate_conversion_on("EUR", "JPY", friday)
+        .await
+        .unwrap();
+    assert_eq!(jpy.rate.value, 162.55);
+
+    // Earlier periods are untouched.
+    let thursday = provider
+        .rate_conversion_on("EUR", "USD", date(2025, 3, 27))
+        .await
+        .unwrap();
+    assert_eq!(thursday.rate.value, 1.0786);
+
+    assert_eq!(http_client.requests().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failed_sync_is_not_recorded() {
+    let http_client = MockHttpClient::new();
+    http_client
+        .respond(DATA_API, fixtures::week())
+        .respond(DATA_API, fixtures::server_error());
+    let clock = Arc::new(ManualClock::at("2025-03-28T17:00:00Z"));
+    let provider = provider(&http_client).with_clock(clock.clone());
+
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap();
+    clock.advance(TimeDelta::hours(1));
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap_err();
+
+    let failed = http_client.requests().len();
+    clock.advance(TimeDelta::hours(1));
+    provider
+        .sync_history(ExrSeries::daily(), date(2025, 3, 24))
+        .await
+        .unwrap_err();
+
+    // The retry still asks for everything since the first, successful, sync.
+    let requests = http_client.requests();
+    assert!(
+        requests[failed]
+            .url
+            .ends_with("updatedAfter=2025-03-28T17%3A00%3A00%2B00%3A00")
+    );
+}
@@ -0,0 +1,31 @@
+use std::{sync::Arc, time::Duration};
+
+use bce_exchange_client::{BceClient, RetryPolicy, eurofxref::EuroFxRefClient};
+use bce_exchange_database_in_memory::InMemoryStorageAdapter;
+use bce_exchange_provider::BceExchangeProvider;
+
+use crate::MockHttpClient;
+
+/// Data API client over `http_client`, retrying three times with short
+/// backoffs.
+pub fn client(http_client: &MockHttpClient) -> BceClient {
+    BceClient::new(Arc::new(http_client.clone())).with_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_secs(1),
+    })
+}
+
+/// `eurofxref` client over `http_client`, retrying twice with short backoffs.
+pub fn eurofxref_client(http_client: &MockHttpClient) -> EuroFxRefClient {
+    EuroFxRefClient::new(Arc::new(http_client.clone())).with_retry_policy(RetryPolicy {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_secs(1),
+    })
+}
+
+/// Provider over `http_client`, storing in memory.
+pub fn provider(http_client: &MockHttpClient) -> BceExchangeProvider {
+    BceExchangeProvider::new(Arc::new(http_client.clone()), InMemoryStorageAdapter::new())
+}
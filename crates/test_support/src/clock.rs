@@ -0,0 +1,28 @@
+use std::sync::Mutex;
+
+use bce_exchange_provider::Clock;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Clock the tests move by hand.
+pub struct ManualClock(Mutex<DateTime<Utc>>);
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self(Mutex::new(now))
+    }
+
+    /// Clock reading the RFC 3339 instant `rfc3339`.
+    pub fn at(rfc3339: &str) -> Self {
+        Self::new(DateTime::parse_from_rfc3339(rfc3339).unwrap().to_utc())
+    }
+
+    pub fn advance(&self, by: TimeDelta) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
@@ -0,0 +1,132 @@
+//! Recorded ECB responses. The data API ones answer an EXR request
+//! for the daily reference rates, the structure ones a `dataflow` or
+//! `codelist` request.
+
+use crate::MockResponse;
+
+/// Fixing of Friday 2025-03-28 for USD, JPY, GBP and CHF.
+pub const EXR_DAILY: &str = include_str!("../fixtures/exr_daily.json");
+/// [`EXR_DAILY`] as SDMX-CSV, which carries no labels for the codes.
+pub const EXR_DAILY_CSV: &str = include_str!("../fixtures/exr_daily.csv");
+/// Fixings of USD and JPY from Monday 2025-03-24 to Friday 2025-03-28.
+pub const EXR_WEEK: &str = include_str!("../fixtures/exr_week.json");
+/// [`EXR_DAILY`] along with RUB, whose series was discontinued and still
+/// reports its last observation of 2022-03-01.
+pub const EXR_DISCONTINUED: &str = include_str!("../fixtures/exr_discontinued.json");
+/// An `updatedAfter` delta revising the USD fixing of 2025-03-28 to 1.0812.
+pub const EXR_REVISION: &str = include_str!("../fixtures/exr_revision.json");
+/// [`EXR_WEEK`], except that the JPY observation of Friday has no value and
+/// the `M` (missing) status.
+pub const EXR_MISSING_OBSERVATION: &str = include_str!("../fixtures/exr_missing_observation.json");
+/// SDMX-CSV fixing of 2025-03-28 for JPY and USD, the JPY observation being
+/// missing.
+pub const EXR_MISSING_OBSERVATION_CSV: &str =
+    include_str!("../fixtures/exr_missing_observation.csv");
+/// A data set without any series or observation.
+pub const EXR_EMPTY: &str = include_str!("../fixtures/exr_empty.json");
+/// A response whose structure lacks the `CURRENCY` dimension.
+pub const EXR_MISSING_CURRENCY: &str = include_str!("../fixtures/exr_missing_currency.json");
+/// SDMX-CSV history of JPY and USD from 2025-03-26 to 2025-03-28. One of
+/// its quoted fields spans two lines.
+pub const EXR_HISTORY_CSV: &str = include_str!("../fixtures/exr_history.csv");
+/// SDMX-ML generic data message with the JPY and USD fixings of 2025-03-27 and
+/// 2025-03-28, where USD of 2025-03-27 is missing and of 2025-03-28
+/// provisional.
+pub const EXR_GENERIC_XML: &str = include_str!("../fixtures/exr_generic.xml");
+/// The observations of [`EXR_GENERIC_XML`] as a structure-specific data
+/// message.
+pub const EXR_STRUCTURE_SPECIFIC_XML: &str = include_str!("../fixtures/exr_structure_specific.xml");
+/// Body of the 404 the data API answers for a period without fixings, such as
+/// a weekend.
+pub const EXR_WEEKEND: &str = include_str!("../fixtures/exr_weekend.txt");
+/// Body of an HTTP 500 from the data API.
+pub const HTTP_500: &str = include_str!("../fixtures/http_500.html");
+/// `eurofxref-daily.xml` carrying the same fixing as [`EXR_DAILY`].
+pub const EUROFXREF_DAILY: &str = include_str!("../fixtures/eurofxref_daily.xml");
+/// `eurofxref-hist-90d.xml` trimmed to the USD and JPY fixings from
+/// 2025-03-26 to 2025-03-28.
+pub const EUROFXREF_HIST_90D: &str = include_str!("../fixtures/eurofxref_hist_90d.xml");
+/// `eurofxref-hist.csv` trimmed to a few dates, newest first. CYP is only
+/// quoted on 1999-01-04, as `N/A` afterwards.
+pub const EUROFXREF_HIST_CSV: &str = include_str!("../fixtures/eurofxref_hist.csv");
+/// `eurofxref-hist.zip`, the archive holding [`EUROFXREF_HIST_CSV`].
+pub const EUROFXREF_HIST_ZIP: &[u8] = include_bytes!("../fixtures/eurofxref_hist.zip");
+/// `CL_CURRENCY`, trimmed to a few codes. Some names carry character and
+/// entity references, and CHF is also named in German.
+pub const CODELIST_CURRENCY: &str = include_str!("../fixtures/codelist_currency.xml");
+/// `CL_EXR_SUFFIX`, the series variations of the EXR dataflow.
+pub const CODELIST_EXR_SUFFIX: &str = include_str!("../fixtures/codelist_exr_suffix.xml");
+/// Every ECB dataflow, trimmed to EST, EXR and ICP.
+pub const DATAFLOWS: &str = include_str!("../fixtures/dataflows.xml");
+
+pub fn normal_day() -> MockResponse {
+    MockResponse::ok(EXR_DAILY).with_header("Content-Type", "application/json")
+}
+
+pub fn week() -> MockResponse {
+    MockResponse::ok(EXR_WEEK).with_header("Content-Type", "application/json")
+}
+
+pub fn discontinued_series() -> MockResponse {
+    MockResponse::ok(EXR_DISCONTINUED).with_header("Content-Type", "application/json")
+}
+
+pub fn missing_observation() -> MockResponse {
+    MockResponse::ok(EXR_MISSING_OBSERVATION).with_header("Content-Type", "application/json")
+}
+
+pub fn revision() -> MockResponse {
+    MockResponse::ok(EXR_REVISION).with_header("Content-Type", "application/json")
+}
+
+pub fn history_csv() -> MockResponse {
+    MockResponse::ok(EXR_HISTORY_CSV).with_header("Content-Type", "text/csv")
+}
+
+pub fn generic_xml() -> MockResponse {
+    MockResponse::ok(EXR_GENERIC_XML).with_header("Content-Type", "application/xml")
+}
+
+pub fn structure_specific_xml() -> MockResponse {
+    MockResponse::ok(EXR_STRUCTURE_SPECIFIC_XML).with_header("Content-Type", "application/xml")
+}
+
+pub fn weekend() -> MockResponse {
+    MockResponse::new(404, EXR_WEEKEND)
+}
+
+pub fn empty_dataset() -> MockResponse {
+    MockResponse::ok(EXR_EMPTY).with_header("Content-Type", "application/json")
+}
+
+pub fn missing_currency_dimension() -> MockResponse {
+    MockResponse::ok(EXR_MISSING_CURRENCY).with_header("Content-Type", "application/json")
+}
+
+pub fn server_error() -> MockResponse {
+    MockResponse::new(500, HTTP_500).with_header("Content-Type", "text/html")
+}
+
+pub fn eurofxref_daily() -> MockResponse {
+    MockResponse::ok(EUROFXREF_DAILY).with_header("Content-Type", "text/xml")
+}
+
+pub fn eurofxref_hist_90d() -> MockResponse {
+    MockResponse::ok(EUROFXREF_HIST_90D).with_header("Content-Type", "text/xml")
+}
+
+pub fn eurofxref_hist_zip() -> MockResponse {
+    MockResponse::ok(EUROFXREF_HIST_ZIP).with_header("Content-Type", "application/zip")
+}
+
+pub fn codelist_currency() -> MockResponse {
+    MockResponse::ok(CODELIST_CURRENCY).with_header("Content-Type", "application/xml")
+}
+
+pub fn codelist_exr_suffix() -> MockResponse {
+    MockResponse::ok(CODELIST_EXR_SUFFIX).with_header("Content-Type", "application/xml")
+}
+
+pub fn dataflows() -> MockResponse {
+    MockResponse::ok(DATAFLOWS).with_header("Content-Type", "application/xml")
+}
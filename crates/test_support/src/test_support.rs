@@ -0,0 +1,187 @@
+//! Offline stand-ins for the ECB: a scriptable [`HttpClient`] and recorded
+//! responses of the data API and the `eurofxref` feeds, along with the
+//! clients, provider and clock the tests build on them.
+
+mod clients;
+mod clock;
+pub mod fixtures;
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use chrono::NaiveDate;
+use futures::future::BoxFuture;
+use http_client::{AsyncBody, HttpClient, Request, Response};
+
+pub use clients::{client, eurofxref_client, provider};
+pub use clock::ManualClock;
+
+/// Base URL of the ECB data API's exchange rates dataflow.
+pub const DATA_API: &str = "https://data-api.ecb.europa.eu/service/data/EXR/";
+
+/// Base URL of the `eurofxref` feeds.
+pub const EUROFXREF: &str = "https://www.ecb.europa.eu/stats/eurofxref/";
+
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// A canned HTTP response.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, body)
+    }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// A request received by [`MockHttpClient`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RecordedRequest {
+    /// Value of the header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+struct Route {
+    url_pattern: String,
+    responses: VecDeque<MockResponse>,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// In-memory [`HttpClient`] answering from scripted responses and recording
+/// every request it receives.
+///
+/// Responses are matched on a substring of the request URL, trying routes in
+/// the order they were first scripted. Responses scripted for the same pattern
+/// are served in turn, the last one being repeated. A request matching no
+/// route fails like a network error would.
+#[derive(Clone, Default)]
+pub struct MockHttpClient {
+    state: Arc<Mutex<State>>,
+}
+
+impl MockHttpClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests whose URL contains `url_pattern` with `response`,
+    /// after any response already scripted for the same pattern.
+    pub fn respond(&self, url_pattern: impl Into<String>, response: MockResponse) -> &Self {
+        let url_pattern = url_pattern.into();
+        let mut state = self.state.lock().unwrap();
+
+        match state
+            .routes
+            .iter_mut()
+            .find(|route| route.url_pattern == url_pattern)
+        {
+            Some(route) => route.responses.push_back(response),
+            None => state.routes.push(Route {
+                url_pattern,
+                responses: VecDeque::from([response]),
+            }),
+        }
+
+        self
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    fn next_response(&self, request: RecordedRequest) -> Option<MockResponse> {
+        let mut state = self.state.lock().unwrap();
+
+        let response = state
+            .routes
+            .iter_mut()
+            .find(|route| request.url.contains(&route.url_pattern))
+            .and_then(|route| {
+                if route.responses.len() > 1 {
+                    route.responses.pop_front()
+                } else {
+                    route.responses.front().cloned()
+                }
+            });
+
+        state.requests.push(request);
+
+        response
+    }
+}
+
+impl HttpClient for MockHttpClient {
+    fn send(
+        &self,
+        request: Request<AsyncBody>,
+    ) -> BoxFuture<'static, anyhow::Result<Response<AsyncBody>>> {
+        let recorded = RecordedRequest {
+            method: request.method().to_string(),
+            url: request.uri().to_string(),
+            headers: request
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        name.to_string(),
+                        String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                    )
+                })
+                .collect(),
+        };
+        let url = recorded.url.clone();
+
+        let response = self.next_response(recorded);
+
+        Box::pin(async move {
+            let Some(mock) = response else {
+                anyhow::bail!("No mock response scripted for {}", url);
+            };
+
+            let mut response = Response::builder().status(mock.status);
+            for (name, value) in &mock.headers {
+                response = response.header(name.as_str(), value.as_str());
+            }
+
+            Ok(response.body(AsyncBody::from(mock.body))?)
+        })
+    }
+}
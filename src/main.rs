@@ -1,9 +1,12 @@
-use std::{env, path::PathBuf, sync::Arc};
+use std::{env, path::PathBuf, sync::Arc, time::Duration};
 
 use anyhow::Result;
 use bce_exchange_database_sqlite::SqliteStorageAdapter;
 use bce_exchange_mcp_primitives::tools::RateConversion;
-use bce_exchange_provider::BceExchangeProvider;
+use bce_exchange_provider::{
+    AlwaysRefresh, BceClientConfig, BceExchangeProvider, CachePolicy, DEFAULT_MAX_STALENESS,
+    FallbackPolicy, FixedTtl, NeverRefresh, PublicationSchedule, RefreshSchedule,
+};
 use context_server::{ContextServer, ContextServerRpcRequest, ContextServerRpcResponse};
 use context_server_utils::{
     prompt_registry::PromptRegistry, resource_registry::ResourceRegistry,
@@ -13,6 +16,9 @@ use http_client::HttpClient;
 use http_client_reqwest::HttpClientReqwest;
 use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt, BufReader};
 
+/// How long startup waits for the currencies listed in the tool description.
+const QUOTED_CURRENCIES_TIMEOUT: Duration = Duration::from_secs(10);
+
 fn get_database_directory() -> Result<PathBuf> {
     let home_dir = env::var("HOME")
         .map_err(|_| anyhow::anyhow!("Could not find HOME environment variable"))?;
@@ -28,8 +34,106 @@ fn get_database_directory() -> Result<PathBuf> {
     Ok(config_dir)
 }
 
+/// Reads the ECB client settings from the environment:
+///
+/// - `BCE_EXCHANGE_BASE_URL`: root of the SDMX web service
+/// - `BCE_EXCHANGE_EUROFXREF_URL`: directory holding the eurofxref feeds
+/// - `BCE_EXCHANGE_USER_AGENT`
+/// - `BCE_EXCHANGE_TIMEOUT_SECS`: per-attempt request timeout
+/// - `BCE_EXCHANGE_HEADERS`: extra headers as `Name: value`, one per line
+fn get_client_config() -> Result<BceClientConfig> {
+    let mut builder = BceClientConfig::builder();
+
+    if let Ok(base_url) = env::var("BCE_EXCHANGE_BASE_URL") {
+        builder = builder.base_url(base_url);
+    }
+
+    if let Ok(eurofxref_url) = env::var("BCE_EXCHANGE_EUROFXREF_URL") {
+        builder = builder.eurofxref_url(eurofxref_url);
+    }
+
+    if let Ok(user_agent) = env::var("BCE_EXCHANGE_USER_AGENT") {
+        builder = builder.user_agent(user_agent);
+    }
+
+    if let Ok(timeout) = env::var("BCE_EXCHANGE_TIMEOUT_SECS") {
+        let seconds = timeout
+            .parse::<u64>()
+            .map_err(|_| anyhow::anyhow!("Invalid BCE_EXCHANGE_TIMEOUT_SECS: {}", timeout))?;
+        builder = builder.timeout(Duration::from_secs(seconds));
+    }
+
+    if let Ok(headers) = env::var("BCE_EXCHANGE_HEADERS") {
+        for header in headers.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, value) = header.split_once(':').ok_or_else(|| {
+                anyhow::anyhow!("Invalid header in BCE_EXCHANGE_HEADERS: {}", header)
+            })?;
+            builder = builder.header(name.trim(), value.trim());
+        }
+    }
+
+    Ok(builder.build())
+}
+
+/// Reads `BCE_EXCHANGE_FALLBACK_POLICY`, one of `previous` (default), `next`
+/// or `strict`: the fixing used when converting on a day without one.
+fn get_fallback_policy() -> Result<FallbackPolicy> {
+    match env::var("BCE_EXCHANGE_FALLBACK_POLICY").as_deref() {
+        Err(_) | Ok("previous") => Ok(FallbackPolicy::PreviousBusinessDay),
+        Ok("next") => Ok(FallbackPolicy::NextBusinessDay),
+        Ok("strict") => Ok(FallbackPolicy::Strict),
+        Ok(policy) => Err(anyhow::anyhow!(
+            "Invalid BCE_EXCHANGE_FALLBACK_POLICY: {}",
+            policy
+        )),
+    }
+}
+
+/// Reads `BCE_EXCHANGE_CACHE_POLICY`, deciding when the latest rates are
+/// fetched again:
+///
+/// - `publication` (default): once the next ECB fixing is due
+/// - `ttl`: after `BCE_EXCHANGE_CACHE_TTL_SECS` seconds
+/// - `never`: never once cached, for offline use
+/// - `always`: on every conversion
+fn get_cache_policy() -> Result<Box<dyn CachePolicy>> {
+    match env::var("BCE_EXCHANGE_CACHE_POLICY").as_deref() {
+        Err(_) | Ok("publication") => Ok(Box::new(PublicationSchedule::default())),
+        Ok("ttl") => {
+            let ttl = env::var("BCE_EXCHANGE_CACHE_TTL_SECS")
+                .map_err(|_| anyhow::anyhow!("BCE_EXCHANGE_CACHE_TTL_SECS is required"))?;
+            let seconds = ttl
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid BCE_EXCHANGE_CACHE_TTL_SECS: {}", ttl))?;
+            Ok(Box::new(FixedTtl(Duration::from_secs(seconds))))
+        }
+        Ok("never") => Ok(Box::new(NeverRefresh)),
+        Ok("always") => Ok(Box::new(AlwaysRefresh)),
+        Ok(policy) => Err(anyhow::anyhow!(
+            "Invalid BCE_EXCHANGE_CACHE_POLICY: {}",
+            policy
+        )),
+    }
+}
+
+/// Reads `BCE_EXCHANGE_MAX_STALENESS_SECS`, how old the stored rates served
+/// during an ECB outage may be, a week by default. `0` disables serving stale
+/// rates.
+fn get_max_staleness() -> Result<Option<Duration>> {
+    let Ok(max_staleness) = env::var("BCE_EXCHANGE_MAX_STALENESS_SECS") else {
+        return Ok(Some(DEFAULT_MAX_STALENESS));
+    };
+
+    let seconds = max_staleness.parse::<u64>().map_err(|_| {
+        anyhow::anyhow!("Invalid BCE_EXCHANGE_MAX_STALENESS_SECS: {}", max_staleness)
+    })?;
+
+    Ok((seconds > 0).then(|| Duration::from_secs(seconds)))
+}
+
 struct ContextServerState {
     rpc: ContextServer,
+    provider: Arc<BceExchangeProvider>,
 }
 
 impl ContextServerState {
@@ -42,10 +146,44 @@ impl ContextServerState {
 
         let db_path = get_database_directory()?.join("exchange.db");
 
-        tool_registry.register(Arc::new(RateConversion::new(BceExchangeProvider::new(
+        let provider = BceExchangeProvider::with_client_config(
             http_client.clone(),
             SqliteStorageAdapter::new(&db_path.to_string_lossy()).await?,
-        ))));
+            get_client_config()?,
+        )
+        .with_cache_policy(get_cache_policy()?)
+        .with_fallback_policy(get_fallback_policy()?)
+        .with_max_staleness(get_max_staleness()?);
+
+        // The tool still works without the list, it only describes the
+        // supported currencies in general terms. Offline, the ECB requests
+        // could retry for minutes, holding up the MCP initialize.
+        let currencies =
+            match tokio::time::timeout(QUOTED_CURRENCIES_TIMEOUT, provider.quoted_currencies())
+                .await
+            {
+                Ok(Ok(currencies)) => currencies,
+                Ok(Err(e)) => {
+                    eprintln!("Error fetching quoted currencies: {}", e);
+                    Vec::new()
+                }
+                Err(_) => {
+                    eprintln!("Timed out fetching quoted currencies");
+                    Vec::new()
+                }
+            };
+
+        let provider = Arc::new(provider);
+
+        // Unless disabled, new fixings are fetched as soon as they are
+        // published rather than by the first conversion after that.
+        if env::var("BCE_EXCHANGE_BACKGROUND_REFRESH").as_deref() != Ok("false") {
+            provider.start_background_refresh(RefreshSchedule::default());
+        }
+
+        tool_registry.register(Arc::new(
+            RateConversion::new(provider.clone()).with_currencies(currencies),
+        ));
 
         Ok(Self {
             rpc: ContextServer::builder()
@@ -54,6 +192,7 @@ impl ContextServerState {
                 .with_resources(resource_registry)
                 .with_prompts(prompt_registry)
                 .build()?,
+            provider,
         })
     }
 
@@ -91,5 +230,7 @@ async fn main() -> Result<()> {
         }
     }
 
+    state.provider.stop_background_refresh().await;
+
     Ok(())
 }